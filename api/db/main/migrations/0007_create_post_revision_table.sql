@@ -0,0 +1,19 @@
+-- SQLite
+CREATE TABLE post_revision (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  post_id INTEGER NOT NULL,
+  author_id INTEGER NOT NULL,
+  reason TEXT NOT NULL,
+  -- snapshot of the post
+  slug TEXT NOT NULL,
+  title TEXT NOT NULL,
+  poster_id INTEGER NOT NULL,
+  short_description TEXT NOT NULL,
+  description TEXT NOT NULL,
+  tag_ids TEXT NOT NULL,
+  published_at TEXT NOT NULL,
+  --
+  created_at TEXT NOT NULL
+);
+CREATE INDEX idx_post_revision_post_id ON post_revision (post_id);
+CREATE INDEX idx_post_revision_created_at ON post_revision (created_at);
@@ -0,0 +1,13 @@
+-- SQLite
+ALTER TABLE post ADD COLUMN expires_at TEXT NOT NULL DEFAULT '';
+ALTER TABLE post ADD COLUMN expiry_reminded_at TEXT NOT NULL DEFAULT '';
+ALTER TABLE post ADD COLUMN archived_at TEXT NOT NULL DEFAULT '';
+ALTER TABLE post ADD COLUMN is_archived BOOLEAN GENERATED ALWAYS AS (archived_at <> '') VIRTUAL;
+CREATE INDEX idx_post_expires_at ON post (expires_at);
+CREATE INDEX idx_post_is_archived ON post (is_archived);
+-- existing posts get the default window, and at least a week to be extended
+UPDATE post
+SET expires_at = MAX(
+  strftime('%Y-%m-%dT%H:%M:%fZ', substr(created_at, 1, 19), '+30 days'),
+  strftime('%Y-%m-%dT%H:%M:%fZ', 'now', '+7 days')
+);
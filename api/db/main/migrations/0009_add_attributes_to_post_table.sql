@@ -0,0 +1,13 @@
+-- SQLite
+-- all optional, the salary columns are either all set or all NULL
+ALTER TABLE post ADD COLUMN wilaya_code INTEGER;
+ALTER TABLE post ADD COLUMN contract_type TEXT;
+ALTER TABLE post ADD COLUMN remote_policy TEXT;
+ALTER TABLE post ADD COLUMN salary_min INTEGER;
+ALTER TABLE post ADD COLUMN salary_max INTEGER;
+ALTER TABLE post ADD COLUMN salary_currency TEXT;
+ALTER TABLE post ADD COLUMN salary_period TEXT;
+CREATE INDEX idx_post_wilaya_code ON post (wilaya_code);
+CREATE INDEX idx_post_contract_type ON post (contract_type);
+CREATE INDEX idx_post_remote_policy ON post (remote_policy);
+CREATE INDEX idx_post_salary_max ON post (salary_max);
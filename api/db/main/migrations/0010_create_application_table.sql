@@ -0,0 +1,17 @@
+-- SQLite
+CREATE TABLE application (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  post_id INTEGER NOT NULL,
+  candidate_name TEXT NOT NULL,
+  candidate_email TEXT NOT NULL,
+  message TEXT NOT NULL,
+  -- a JSON array of urls
+  links TEXT NOT NULL DEFAULT '[]',
+  status TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL DEFAULT ''
+);
+CREATE INDEX idx_application_post_id ON application (post_id);
+CREATE INDEX idx_application_candidate_email ON application (candidate_email);
+CREATE INDEX idx_application_status ON application (status);
+CREATE INDEX idx_application_created_at ON application (created_at);
@@ -0,0 +1,22 @@
+-- SQLite
+-- raw events, kept until their day is over and counted into post_stat
+CREATE TABLE post_event (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  post_id INTEGER NOT NULL,
+  kind TEXT NOT NULL,
+  -- a salted hash that changes every day, never the ip itself
+  ip_hash TEXT NOT NULL,
+  -- YYYY-MM-DD in UTC
+  day TEXT NOT NULL,
+  created_at TEXT NOT NULL
+);
+CREATE UNIQUE INDEX idx_post_event_unique ON post_event (post_id, kind, ip_hash, day);
+CREATE INDEX idx_post_event_day ON post_event (day);
+
+CREATE TABLE post_stat (
+  post_id INTEGER NOT NULL,
+  day TEXT NOT NULL,
+  view_count INTEGER NOT NULL DEFAULT 0,
+  apply_click_count INTEGER NOT NULL DEFAULT 0,
+  PRIMARY KEY (post_id, day)
+);
@@ -0,0 +1,8 @@
+-- SQLite
+-- posts published before moderation existed stay live
+ALTER TABLE post ADD COLUMN moderation_status TEXT NOT NULL DEFAULT 'approved';
+ALTER TABLE post ADD COLUMN moderation_reason TEXT NOT NULL DEFAULT '';
+ALTER TABLE post ADD COLUMN moderated_at TEXT NOT NULL DEFAULT '';
+CREATE INDEX idx_post_moderation_status ON post (moderation_status);
+
+ALTER TABLE account ADD COLUMN is_trusted INTEGER NOT NULL DEFAULT 0;
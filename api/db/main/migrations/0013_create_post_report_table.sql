@@ -0,0 +1,13 @@
+-- SQLite
+CREATE TABLE post_report (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  post_id INTEGER NOT NULL,
+  reason TEXT NOT NULL,
+  message TEXT NOT NULL DEFAULT '',
+  -- a salted hash of the reporter's ip, never the ip itself
+  reporter_hash TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  reviewed_at TEXT NOT NULL DEFAULT ''
+);
+CREATE UNIQUE INDEX idx_post_report_unique ON post_report (post_id, reporter_hash);
+CREATE INDEX idx_post_report_reviewed_at ON post_report (reviewed_at);
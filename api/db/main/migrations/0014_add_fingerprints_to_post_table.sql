@@ -0,0 +1,7 @@
+-- SQLite
+-- posts created before this migration are left out of the duplicate detection
+ALTER TABLE post ADD COLUMN content_hash TEXT NOT NULL DEFAULT '';
+ALTER TABLE post ADD COLUMN minhash TEXT NOT NULL DEFAULT '';
+-- set when the post was flagged as a near duplicate of another one
+ALTER TABLE post ADD COLUMN similar_post_id INTEGER;
+CREATE INDEX idx_post_content_hash ON post (content_hash);
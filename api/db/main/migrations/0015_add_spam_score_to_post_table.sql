@@ -0,0 +1,5 @@
+-- SQLite
+-- posts created before this migration weren't scored
+ALTER TABLE post ADD COLUMN spam_score REAL NOT NULL DEFAULT 0;
+-- json array of the matched rules, with their weights and explanations
+ALTER TABLE post ADD COLUMN spam_rules TEXT NOT NULL DEFAULT '[]';
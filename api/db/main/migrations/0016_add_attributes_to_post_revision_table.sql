@@ -0,0 +1,10 @@
+-- SQLite
+-- snapshot of the post's attributes and expiry, revisions written before them have none
+ALTER TABLE post_revision ADD COLUMN expires_at TEXT NOT NULL DEFAULT '';
+ALTER TABLE post_revision ADD COLUMN wilaya_code INTEGER;
+ALTER TABLE post_revision ADD COLUMN contract_type TEXT;
+ALTER TABLE post_revision ADD COLUMN remote_policy TEXT;
+ALTER TABLE post_revision ADD COLUMN salary_min INTEGER;
+ALTER TABLE post_revision ADD COLUMN salary_max INTEGER;
+ALTER TABLE post_revision ADD COLUMN salary_currency TEXT;
+ALTER TABLE post_revision ADD COLUMN salary_period TEXT;
@@ -0,0 +1,4 @@
+-- SQLite
+-- moderation decisions and reports are recorded as revisions too
+ALTER TABLE post_revision ADD COLUMN moderation_status TEXT NOT NULL DEFAULT 'approved';
+ALTER TABLE post_revision ADD COLUMN moderation_reason TEXT NOT NULL DEFAULT '';
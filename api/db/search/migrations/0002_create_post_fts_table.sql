@@ -0,0 +1,11 @@
+-- SQLite
+CREATE VIRTUAL TABLE post_fts USING fts5 (
+  title,
+  poster_display_name,
+  short_description,
+  tag_names,
+  description,
+  tokenize = 'unicode61 remove_diacritics 2',
+  prefix = '2 3'
+);
+CREATE VIRTUAL TABLE post_fts_vocab USING fts5vocab (post_fts, 'col');
@@ -0,0 +1,6 @@
+-- SQLite
+-- indexed words have no stems yet, clearing the indexes lets the re-indexing cron job index all the posts again
+DELETE FROM word;
+DELETE FROM post_fts;
+ALTER TABLE word ADD COLUMN stem TEXT NOT NULL DEFAULT '';
+CREATE INDEX idx_word_stem ON word (stem);
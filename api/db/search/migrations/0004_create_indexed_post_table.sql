@@ -0,0 +1,20 @@
+-- SQLite
+-- holds what search results can be filtered by, kept in sync with the search indexes
+CREATE TABLE indexed_post (
+  id INTEGER PRIMARY KEY,
+  poster_id INTEGER NOT NULL,
+  published_at TEXT NOT NULL
+);
+CREATE INDEX idx_indexed_post_poster_id ON indexed_post (poster_id);
+CREATE INDEX idx_indexed_post_published_at ON indexed_post (published_at);
+
+CREATE TABLE indexed_post_tag (
+  post_id INTEGER NOT NULL,
+  tag_id INTEGER NOT NULL,
+  PRIMARY KEY (post_id, tag_id)
+);
+CREATE INDEX idx_indexed_post_tag_tag_id ON indexed_post_tag (tag_id);
+
+-- already indexed posts have no filter data, clearing the indexes lets the re-indexing cron job index all the posts again
+DELETE FROM word;
+DELETE FROM post_fts;
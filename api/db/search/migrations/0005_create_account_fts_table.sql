@@ -0,0 +1,7 @@
+-- SQLite
+CREATE VIRTUAL TABLE account_fts USING fts5 (
+  display_name,
+  slug,
+  tokenize = 'unicode61 remove_diacritics 2',
+  prefix = '2 3'
+);
@@ -0,0 +1,16 @@
+-- SQLite
+ALTER TABLE indexed_post ADD COLUMN wilaya_code INTEGER;
+ALTER TABLE indexed_post ADD COLUMN contract_type TEXT;
+ALTER TABLE indexed_post ADD COLUMN remote_policy TEXT;
+ALTER TABLE indexed_post ADD COLUMN salary_max INTEGER;
+ALTER TABLE indexed_post ADD COLUMN salary_currency TEXT;
+ALTER TABLE indexed_post ADD COLUMN salary_period TEXT;
+CREATE INDEX idx_indexed_post_wilaya_code ON indexed_post (wilaya_code);
+CREATE INDEX idx_indexed_post_contract_type ON indexed_post (contract_type);
+CREATE INDEX idx_indexed_post_remote_policy ON indexed_post (remote_policy);
+
+-- already indexed posts have no attributes, clearing the post indexes lets the re-indexing cron job index all the posts again
+DELETE FROM word WHERE model_type = 'post';
+DELETE FROM post_fts;
+DELETE FROM indexed_post_tag;
+DELETE FROM indexed_post;
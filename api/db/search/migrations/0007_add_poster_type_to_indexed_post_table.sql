@@ -0,0 +1,9 @@
+-- SQLite
+ALTER TABLE indexed_post ADD COLUMN poster_type TEXT NOT NULL DEFAULT '';
+CREATE INDEX idx_indexed_post_poster_type ON indexed_post (poster_type);
+
+-- already indexed posts have no poster type, clearing the post indexes lets the re-indexing cron job index all the posts again
+DELETE FROM word WHERE model_type = 'post';
+DELETE FROM post_fts;
+DELETE FROM indexed_post_tag;
+DELETE FROM indexed_post;
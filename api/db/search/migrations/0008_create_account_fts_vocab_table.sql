@@ -0,0 +1,2 @@
+-- SQLite
+CREATE VIRTUAL TABLE account_fts_vocab USING fts5vocab (account_fts, 'col');
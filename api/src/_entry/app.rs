@@ -1,11 +1,16 @@
 use crate::{
   _utils::error::BootError,
   account::controller::create_account_router,
+  application::controller::create_application_router,
   auth::controller::create_auth_router,
   imported_content::{
     controller::create_imported_content_router, cron_job::ImportedContentCronJob,
   },
-  post::controller::create_post_router,
+  moderation::controller::create_moderation_router,
+  post::{controller::create_post_router, cron_job::PostCronJob},
+  post_report::controller::create_post_report_router,
+  post_revision::controller::create_post_revision_router,
+  post_stat::{controller::create_post_stat_router, cron_job::PostStatCronJob},
   search::{controller::create_search_router, cron_job::SearchCronJob},
   tag::controller::create_tag_router,
   web::controller::create_web_router,
@@ -62,13 +67,21 @@ async fn create_app(app_state: AppState) -> Result<Router, BootError> {
   let app = Router::new();
   let app = app
     // @TODO-ZM: align on model naming convention
-    .nest("/posts", create_post_router())
+    .nest(
+      "/posts",
+      create_post_router()
+        .merge(create_post_revision_router())
+        .merge(create_application_router())
+        .merge(create_post_stat_router())
+        .merge(create_post_report_router()),
+    )
     .nest("/search", create_search_router())
     .nest("/accounts", create_account_router())
     .nest("/tags", create_tag_router())
     .nest("/auth", create_auth_router())
     .nest("/web/", create_web_router())
     .nest("/imported_content", create_imported_content_router())
+    .nest("/moderation", create_moderation_router())
     .route(
       "/",
       get(|| async {
@@ -99,8 +112,13 @@ async fn create_cron_jobs(app_state: AppState) -> Result<JobScheduler, BootError
   let imported_content = Arc::new(ImportedContentCronJob {
     app_state: app_state.clone(),
   });
+  let post_cron_job = Arc::new(PostCronJob {
+    app_state: app_state.clone(),
+  });
+  let post_stat_cron_job = Arc::new(PostStatCronJob {
+    app_state: app_state.clone(),
+  });
 
-  // @TODO-ZM: add un-indexing cron job
   let registration_result = schedule
     .add(search_cron_job.create_indexing_cron_job().unwrap())
     .await;
@@ -112,6 +130,28 @@ async fn create_cron_jobs(app_state: AppState) -> Result<JobScheduler, BootError
     return Err(BootError::CronJobSetupError);
   }
 
+  let registration_result = schedule
+    .add(search_cron_job.create_unindexing_cron_job().unwrap())
+    .await;
+  if registration_result.is_err() {
+    tracing::error!(
+      "Error while registering un-indexing cron job: {:?}",
+      registration_result.err()
+    );
+    return Err(BootError::CronJobSetupError);
+  }
+
+  let registration_result = schedule
+    .add(search_cron_job.create_reindexing_cron_job().unwrap())
+    .await;
+  if registration_result.is_err() {
+    tracing::error!(
+      "Error while registering re-indexing cron job: {:?}",
+      registration_result.err()
+    );
+    return Err(BootError::CronJobSetupError);
+  }
+
   let registration_result = schedule
     .add(search_cron_job.create_bk_tree_refresher_cron_job().unwrap())
     .await;
@@ -138,5 +178,49 @@ async fn create_cron_jobs(app_state: AppState) -> Result<JobScheduler, BootError
     return Err(BootError::CronJobSetupError);
   }
 
+  let registration_result = schedule
+    .add(post_cron_job.create_publishing_cron_job().unwrap())
+    .await;
+  if registration_result.is_err() {
+    tracing::error!(
+      "Error while registering publishing cron job: {:?}",
+      registration_result.err()
+    );
+    return Err(BootError::CronJobSetupError);
+  }
+
+  let registration_result = schedule
+    .add(post_cron_job.create_archiving_cron_job().unwrap())
+    .await;
+  if registration_result.is_err() {
+    tracing::error!(
+      "Error while registering archiving cron job: {:?}",
+      registration_result.err()
+    );
+    return Err(BootError::CronJobSetupError);
+  }
+
+  let registration_result = schedule
+    .add(post_cron_job.create_expiry_reminder_cron_job().unwrap())
+    .await;
+  if registration_result.is_err() {
+    tracing::error!(
+      "Error while registering expiry reminder cron job: {:?}",
+      registration_result.err()
+    );
+    return Err(BootError::CronJobSetupError);
+  }
+
+  let registration_result = schedule
+    .add(post_stat_cron_job.create_aggregating_cron_job().unwrap())
+    .await;
+  if registration_result.is_err() {
+    tracing::error!(
+      "Error while registering post stat aggregating cron job: {:?}",
+      registration_result.err()
+    );
+    return Err(BootError::CronJobSetupError);
+  }
+
   Ok(schedule)
 }
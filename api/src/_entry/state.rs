@@ -3,13 +3,18 @@ use crate::{
   _utils::error::BootError,
   account::repository::AccountRepository,
   ai::service::AIService,
+  application::repository::ApplicationRepository,
   auth::service::AuthService,
   config::service::ConfigService,
   email::service::EmailService,
   imported_content::{repository::ImportedContentRepository, service::ImportedContentService},
   post::repository::PostRepository,
+  post_report::repository::PostReportRepository,
+  post_revision::repository::PostRevisionRepository,
+  post_stat::repository::PostStatRepository,
   search::service::SearchService,
   security::service::SecurityService,
+  spam::service::SpamService,
   tag::repository::TagRepository,
   task::repository::TaskRepository,
 };
@@ -20,6 +25,10 @@ pub struct AppState {
   // @TODO-ZM: remove this from app state
   pub main_kv_db: Arc<sled::Db>,
   pub post_repository: Arc<PostRepository>,
+  pub post_revision_repository: Arc<PostRevisionRepository>,
+  pub application_repository: Arc<ApplicationRepository>,
+  pub post_stat_repository: Arc<PostStatRepository>,
+  pub post_report_repository: Arc<PostReportRepository>,
   pub tag_repository: Arc<TagRepository>,
   pub account_repository: Arc<AccountRepository>,
   pub config_service: Arc<ConfigService>,
@@ -28,6 +37,7 @@ pub struct AppState {
   pub email_service: Arc<EmailService>,
   pub security_service: Arc<SecurityService>,
   pub ai_service: Arc<AIService>,
+  pub spam_service: Arc<SpamService>,
   pub auth_service: Arc<AuthService>,
   pub imported_content_service: Arc<ImportedContentService>,
   pub imported_content_repository: Arc<ImportedContentRepository>,
@@ -61,8 +71,15 @@ pub async fn create_app_state() -> Result<AppState, BootError> {
     .await?,
   );
 
-  let search_service = Arc::new(SearchService::new(Arc::clone(&search_sql_db)));
+  let search_service = Arc::new(SearchService::new(
+    Arc::clone(&config_service),
+    Arc::clone(&search_sql_db),
+  ));
   let post_repository = Arc::new(PostRepository::new(Arc::clone(&main_sql_db)));
+  let post_revision_repository = Arc::new(PostRevisionRepository::new(Arc::clone(&main_sql_db)));
+  let application_repository = Arc::new(ApplicationRepository::new(Arc::clone(&main_sql_db)));
+  let post_stat_repository = Arc::new(PostStatRepository::new(Arc::clone(&main_sql_db)));
+  let post_report_repository = Arc::new(PostReportRepository::new(Arc::clone(&main_sql_db)));
   let tag_repository = Arc::new(TagRepository::new(Arc::clone(&main_sql_db)));
   let account_repository = Arc::new(AccountRepository::new(Arc::clone(&main_sql_db)));
   let task_repository = Arc::new(TaskRepository::new(Arc::clone(&main_sql_db)));
@@ -76,6 +93,7 @@ pub async fn create_app_state() -> Result<AppState, BootError> {
   ));
   let security_service = Arc::new(SecurityService::new(Arc::clone(&rate_limit_kv_db)));
   let ai_service = Arc::new(AIService::new(Arc::clone(&config_service)));
+  let spam_service = Arc::new(SpamService::new(Arc::clone(&config_service))?);
   let imported_content_service = Arc::new(ImportedContentService::new(Arc::clone(
     &imported_content_repository,
   )));
@@ -83,6 +101,10 @@ pub async fn create_app_state() -> Result<AppState, BootError> {
   Ok(AppState {
     main_kv_db: Arc::clone(&main_kv_db),
     post_repository: Arc::clone(&post_repository),
+    post_revision_repository: Arc::clone(&post_revision_repository),
+    application_repository: Arc::clone(&application_repository),
+    post_stat_repository: Arc::clone(&post_stat_repository),
+    post_report_repository: Arc::clone(&post_report_repository),
     tag_repository: Arc::clone(&tag_repository),
     account_repository: Arc::clone(&account_repository),
     config_service: Arc::clone(&config_service),
@@ -91,6 +113,7 @@ pub async fn create_app_state() -> Result<AppState, BootError> {
     email_service: Arc::clone(&email_service),
     security_service: Arc::clone(&security_service),
     ai_service: Arc::clone(&ai_service),
+    spam_service: Arc::clone(&spam_service),
     auth_service: Arc::clone(&auth_service),
     imported_content_service: Arc::clone(&imported_content_service),
     imported_content_repository: Arc::clone(&imported_content_repository),
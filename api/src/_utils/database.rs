@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use strum_macros::Display;
 
 #[derive(Debug, Deserialize)]
@@ -6,6 +6,13 @@ pub struct DBCount {
   pub count: u32,
 }
 
+// how many rows share a value
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DBFacetCount {
+  pub value: String,
+  pub count: u32,
+}
+
 #[derive(Display)]
 pub enum DBOrderDirection {
   DESC,
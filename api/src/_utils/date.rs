@@ -0,0 +1,18 @@
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+
+// the format dates are stored with, eg: published_at, expires_at
+pub fn format_date(date: &DateTime<Utc>) -> String {
+  date.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()
+}
+
+// accepts a date or a date-time, a date being its midnight in UTC
+pub fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+  if let Ok(date_time) = DateTime::parse_from_rfc3339(date) {
+    return Some(date_time.with_timezone(&Utc));
+  }
+
+  NaiveDate::parse_from_str(date, "%Y-%m-%d")
+    .ok()
+    .and_then(|date| date.and_hms_opt(0, 0, 0))
+    .map(|date_time| Utc.from_utc_datetime(&date_time))
+}
@@ -1,6 +1,7 @@
 #[derive(Debug)]
 pub enum DataAccessError {
   NotFound,
+  AlreadyExists,
   InternalError,
 }
 
@@ -9,6 +10,7 @@ pub enum BootError {
   DBSetupError,
   KVDBSetupError,
   CronJobSetupError,
+  SpamRulesSetupError,
 }
 
 #[derive(Debug)]
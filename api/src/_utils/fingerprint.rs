@@ -0,0 +1,82 @@
+use super::string::{get_words, normalize_text};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+// how many hash functions a minhash is made of, more is more precise and bigger to store
+const MINHASH_SIZE: usize = 64;
+// word triples, so a changed word only changes a few of them
+const SHINGLE_SIZE: usize = 3;
+
+// fingerprints are stored, so they need a hash that's stable across builds, unlike std's DefaultHasher
+fn get_fnv1a_hash(bytes: &[u8]) -> u64 {
+  bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+    (hash ^ *byte as u64).wrapping_mul(FNV_PRIME)
+  })
+}
+
+// one of the MINHASH_SIZE hash functions, a splitmix64 round over the shingle's hash
+fn get_seeded_hash(hash: u64, seed: u64) -> u32 {
+  let mut hash = hash ^ seed.wrapping_mul(0x9e3779b97f4a7c15);
+  hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+  hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d049bb133111eb);
+
+  ((hash ^ (hash >> 31)) >> 32) as u32
+}
+
+fn parse_minhash(minhash: &str) -> Option<Vec<u32>> {
+  if minhash.len() != MINHASH_SIZE * 8 {
+    return None;
+  }
+
+  (0..MINHASH_SIZE)
+    .map(|index| u32::from_str_radix(minhash.get(index * 8..index * 8 + 8)?, 16).ok())
+    .collect()
+}
+
+pub fn get_content_tokens(title: &str, description: &str) -> Vec<String> {
+  let content = normalize_text(&format!("{} {}", title, description));
+
+  get_words(&content).map(|word| word.to_string()).collect()
+}
+
+// equal for the same words in the same order, whatever the casing, accents and punctuation
+pub fn get_content_hash(tokens: &[String]) -> String {
+  format!("{:016x}", get_fnv1a_hash(tokens.join(" ").as_bytes()))
+}
+
+pub fn get_minhash(tokens: &[String]) -> String {
+  let shingles = match tokens.len() {
+    0..=SHINGLE_SIZE => vec![tokens.join(" ")],
+    _ => tokens
+      .windows(SHINGLE_SIZE)
+      .map(|shingle| shingle.join(" "))
+      .collect::<Vec<String>>(),
+  };
+
+  let mut minhash = [u32::MAX; MINHASH_SIZE];
+  for shingle in shingles {
+    let hash = get_fnv1a_hash(shingle.as_bytes());
+    for (seed, min_hash) in minhash.iter_mut().enumerate() {
+      *min_hash = (*min_hash).min(get_seeded_hash(hash, seed as u64));
+    }
+  }
+
+  minhash
+    .iter()
+    .map(|min_hash| format!("{:08x}", min_hash))
+    .collect()
+}
+
+// estimates the jaccard similarity of the two contents' shingles, from 0 to 1
+pub fn get_minhash_similarity(minhash: &str, other_minhash: &str) -> Option<f64> {
+  let minhash = parse_minhash(minhash)?;
+  let other_minhash = parse_minhash(other_minhash)?;
+
+  let equal_count = minhash
+    .iter()
+    .zip(other_minhash.iter())
+    .filter(|(min_hash, other_min_hash)| min_hash == other_min_hash)
+    .count();
+
+  Some(equal_count as f64 / MINHASH_SIZE as f64)
+}
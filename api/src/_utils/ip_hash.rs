@@ -0,0 +1,20 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::net::IpAddr;
+
+// ips are never stored, the salt scopes the hash to its use, eg: a day for post stats,
+// the hash is keyed and stable across restarts and rust releases, as it's compared with stored ones
+pub fn get_ip_hash(ip: &IpAddr, salt: &str, secret: &str) -> String {
+  // hmac accepts keys of any length
+  let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+  mac.update(salt.as_bytes());
+  mac.update(b"|");
+  mac.update(ip.to_string().as_bytes());
+
+  mac
+    .finalize()
+    .into_bytes()
+    .iter()
+    .map(|byte| format!("{:02x}", byte))
+    .collect()
+}
@@ -0,0 +1,73 @@
+use ammonia::UrlRelative;
+use pulldown_cmark::{html, Event, HeadingLevel, Options, Parser, Tag};
+use std::collections::HashSet;
+
+fn get_markdown_parser(markdown: &str) -> Parser<'_, '_> {
+  Parser::new_ext(markdown, Options::ENABLE_STRIKETHROUGH)
+}
+
+// a safe subset: no images, no tables, and headings never outrank the post's title
+pub fn render_markdown(markdown: &str) -> String {
+  let events = get_markdown_parser(markdown).map(|event| match event {
+    // descriptions written before markdown was supported rely on their line breaks
+    Event::SoftBreak => Event::HardBreak,
+    Event::Start(Tag::Heading(level, id, classes)) => {
+      Event::Start(Tag::Heading(level.max(HeadingLevel::H3), id, classes))
+    }
+    Event::End(Tag::Heading(level, id, classes)) => {
+      Event::End(Tag::Heading(level.max(HeadingLevel::H3), id, classes))
+    }
+    _ => event,
+  });
+
+  let mut unsafe_html = String::new();
+  html::push_html(&mut unsafe_html, events);
+
+  // raw html is allowed in markdown, everything outside of the allow-list is dropped here
+  ammonia::Builder::empty()
+    .add_tags(&[
+      "p",
+      "br",
+      "hr",
+      "strong",
+      "em",
+      "del",
+      "ul",
+      "ol",
+      "li",
+      "blockquote",
+      "code",
+      "pre",
+      "h3",
+      "h4",
+      "h5",
+      "h6",
+      "a",
+    ])
+    .add_tag_attributes("a", &["href"])
+    .add_tag_attributes("ol", &["start"])
+    .url_schemes(HashSet::from(["http", "https", "mailto"]))
+    // relative links, eg: "//evil.com" or "/login", would point at our own pages
+    .url_relative(UrlRelative::Deny)
+    .link_rel(Some("noopener noreferrer nofollow"))
+    .clean(&unsafe_html)
+    .to_string()
+}
+
+// what's read on the page, without the markup nor the links' urls, eg: for search and summaries
+pub fn get_markdown_text(markdown: &str) -> String {
+  let mut text = String::new();
+
+  for event in get_markdown_parser(markdown) {
+    match event {
+      Event::Text(s) | Event::Code(s) => text.push_str(&s),
+      Event::SoftBreak | Event::HardBreak | Event::Rule => text.push('\n'),
+      Event::End(
+        Tag::Paragraph | Tag::Heading(..) | Tag::Item | Tag::CodeBlock(_) | Tag::BlockQuote,
+      ) => text.push('\n'),
+      _ => {}
+    }
+  }
+
+  text.trim().to_string()
+}
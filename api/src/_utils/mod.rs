@@ -1,7 +1,13 @@
 pub mod database;
+pub mod date;
 pub mod error;
+pub mod fingerprint;
+pub mod ip_hash;
+pub mod markdown;
 pub mod post_long_title;
 pub mod post_url;
 pub mod query;
+pub mod stop_words;
 pub mod string;
 pub mod vec;
+pub mod wilaya;
@@ -1,3 +1,4 @@
+use super::wilaya::get_wilaya_name;
 use crate::{
   account::model::{Account, AccountNameTrait, AccountType},
   post::model::Post,
@@ -13,5 +14,10 @@ pub fn get_post_long_title(post: &Post, poster: &Account) -> String {
     poster.get_display_name()
   );
 
-  format!("{}{}", post.title, poster_extension)
+  let location_extension = match post.attributes.wilaya_code.and_then(get_wilaya_name) {
+    Some(wilaya_name) => format!(" in {}", wilaya_name),
+    None => "".to_string(),
+  };
+
+  format!("{}{}{}", post.title, poster_extension, location_extension)
 }
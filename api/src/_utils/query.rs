@@ -1,14 +1,154 @@
 use serde::Deserialize;
 
+use super::wilaya::is_wilaya_code;
+use crate::{
+  application::model::ApplicationStatus,
+  post::model::{
+    ContractType, PostAttributeFilters, PostModerationStatus, RemotePolicy, SalaryCurrency,
+    SalaryPeriod,
+  },
+  search::model::SearchSort,
+};
+
+const DEFAULT_PER_PAGE: u32 = 20;
+const MAX_SEARCH_PER_PAGE: u32 = 100;
+
+fn default_per_page() -> u32 {
+  DEFAULT_PER_PAGE
+}
+
 #[derive(Deserialize)]
 pub struct PaginationQuery {
+  #[serde(default)]
+  page: u32,
+  #[serde(default = "default_per_page")]
+  per_page: u32,
+}
+
+// searches are scored over every match, so their pages are bounded
+#[derive(Deserialize)]
+pub struct SearchPaginationQuery {
+  #[serde(default)]
   page: u32,
+  #[serde(default = "default_per_page")]
   per_page: u32,
 }
 
 #[derive(Deserialize)]
 pub struct SearchQuery {
   pub query: String,
+  pub cursor: Option<String>,
+  // comma separated tag slugs, posts must have all of them
+  pub tags: Option<String>,
+  pub poster_id: Option<u32>,
+  pub published_after: Option<String>,
+  #[serde(default)]
+  pub sort: SearchSort,
+}
+
+#[derive(Deserialize)]
+pub struct FeedQuery {
+  pub cursor: Option<String>,
+  // comma separated tag slugs, posts must have all of them
+  pub tags: Option<String>,
+  pub poster_id: Option<u32>,
+}
+
+pub fn split_comma_separated_values(values: &Option<String>) -> Vec<String> {
+  values
+    .as_ref()
+    .map(|values| {
+      values
+        .split(',')
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .collect::<Vec<String>>()
+    })
+    .unwrap_or(vec![])
+}
+
+// for searches that only take a query, eg: accounts
+#[derive(Deserialize)]
+pub struct TextSearchQuery {
+  pub query: String,
+}
+
+#[derive(Deserialize)]
+pub struct SuggestQuery {
+  pub prefix: String,
+}
+
+// filters the feed and the post search by the posts' attributes
+#[derive(Deserialize)]
+pub struct PostAttributeQuery {
+  pub wilaya: Option<u32>,
+  pub contract_type: Option<ContractType>,
+  pub remote_policy: Option<RemotePolicy>,
+  pub salary_currency: Option<SalaryCurrency>,
+  pub salary_period: Option<SalaryPeriod>,
+  pub min_salary: Option<u32>,
+}
+
+pub trait PostAttributeQueryTrait {
+  fn to_filters(&self) -> Option<PostAttributeFilters>;
+}
+
+impl PostAttributeQueryTrait for PostAttributeQuery {
+  // salaries are only comparable within the same currency and period
+  fn to_filters(&self) -> Option<PostAttributeFilters> {
+    if self.wilaya.is_some_and(|wilaya| !is_wilaya_code(wilaya)) {
+      return None;
+    }
+
+    if self.min_salary.is_some() && (self.salary_currency.is_none() || self.salary_period.is_none())
+    {
+      return None;
+    }
+
+    Some(PostAttributeFilters {
+      wilaya_code: self.wilaya,
+      contract_type: self.contract_type.clone(),
+      remote_policy: self.remote_policy.clone(),
+      salary_currency: self.salary_currency.clone(),
+      salary_period: self.salary_period.clone(),
+      min_salary: self.min_salary,
+    })
+  }
+}
+
+// facets are counted over the whole result set, so they are only returned when asked for
+#[derive(Deserialize)]
+pub struct FacetQuery {
+  #[serde(default)]
+  pub facets: bool,
+}
+
+#[derive(Deserialize)]
+pub struct ExtendPostQuery {
+  pub code: String,
+}
+
+// defaults to the pending posts
+#[derive(Deserialize)]
+pub struct ModerationQueueQuery {
+  pub status: Option<PostModerationStatus>,
+}
+
+#[derive(Deserialize)]
+pub struct ApplicationListQuery {
+  pub status: Option<ApplicationStatus>,
+}
+
+// how many days back the stats go, today included
+#[derive(Deserialize)]
+pub struct PostStatQuery {
+  pub days: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct PostRevisionDiffQuery {
+  pub from: u32,
+  pub to: u32,
 }
 
 pub struct DBPaginationQuery {
@@ -29,6 +169,15 @@ impl PaginationQueryTrait for PaginationQuery {
   }
 }
 
+impl PaginationQueryTrait for SearchPaginationQuery {
+  fn to_db_query(&self) -> DBPaginationQuery {
+    let limit = self.per_page.clamp(1, MAX_SEARCH_PER_PAGE);
+    let start = self.page.saturating_mul(limit);
+
+    DBPaginationQuery { limit, start }
+  }
+}
+
 #[derive(Deserialize)]
 pub struct ImportedContentStatusQuery {
   pub url: String,
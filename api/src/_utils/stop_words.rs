@@ -0,0 +1,65 @@
+// words are listed in their normalized form, see normalize_text
+
+// "it" and single letters like "c" or "r" are left out on purpose, they are meaningful in job posts
+const ENGLISH_STOP_WORDS: &[&str] = &[
+  "a", "about", "after", "all", "also", "an", "and", "any", "are", "as", "at", "be", "been", "but",
+  "by", "can", "do", "for", "from", "has", "have", "he", "her", "his", "how", "if", "in", "into",
+  "is", "its", "more", "not", "of", "on", "or", "our", "she", "so", "such", "than", "that", "the",
+  "their", "them", "then", "there", "these", "they", "this", "to", "was", "we", "were", "what",
+  "when", "where", "which", "who", "will", "with", "you", "your",
+];
+
+const FRENCH_STOP_WORDS: &[&str] = &[
+  "a", "afin", "au", "aux", "avec", "ce", "ces", "cet", "cette", "d", "dans", "de", "des", "du",
+  "elle", "en", "est", "et", "etre", "il", "ils", "j", "je", "l", "la", "le", "les", "leur",
+  "leurs", "lui", "mais", "n", "ne", "nos", "notre", "nous", "on", "ou", "par", "pas", "pour",
+  "qu", "que", "qui", "s", "sa", "se", "ses", "son", "sont", "sur", "un", "une", "vos", "votre",
+  "vous",
+];
+
+const ARABIC_STOP_WORDS: &[&str] = &[
+  "او",
+  "اذا",
+  "الي",
+  "التي",
+  "الذي",
+  "الذين",
+  "ان",
+  "انه",
+  "بعد",
+  "بين",
+  "ثم",
+  "حتي",
+  "عن",
+  "عند",
+  "في",
+  "قد",
+  "كان",
+  "كل",
+  "كما",
+  "لا",
+  "لم",
+  "لن",
+  "ما",
+  "مع",
+  "من",
+  "نحن",
+  "هذا",
+  "هذه",
+  "هو",
+  "هي",
+  "و",
+  "يا",
+];
+
+fn is_arabic_char(c: char) -> bool {
+  matches!(c, '\u{0600}'..='\u{06FF}' | '\u{0750}'..='\u{077F}')
+}
+
+pub fn is_stop_word(word: &str) -> bool {
+  if word.chars().any(is_arabic_char) {
+    return ARABIC_STOP_WORDS.contains(&word);
+  }
+
+  ENGLISH_STOP_WORDS.contains(&word) || FRENCH_STOP_WORDS.contains(&word)
+}
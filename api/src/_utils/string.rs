@@ -1,3 +1,7 @@
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+use super::stop_words::is_stop_word;
+
 pub fn slugify(s: &String) -> String {
   let filtered = s
     .chars()
@@ -23,7 +27,14 @@ pub fn escape_new_line_with_br(s: &String) -> String {
   s.replace("\n", "<br>")
 }
 
-// @TODO-ZM: change this to get_searchable_words
+pub fn escape_html(s: &str) -> String {
+  s.replace("&", "&amp;")
+    .replace("<", "&lt;")
+    .replace(">", "&gt;")
+    .replace("\"", "&quot;")
+    .replace("'", "&#39;")
+}
+
 pub fn get_words<'a>(paragraph: &'a str) -> impl Iterator<Item = &'a str> {
   paragraph
     .split(|c: char| !c.is_alphanumeric())
@@ -31,12 +42,43 @@ pub fn get_words<'a>(paragraph: &'a str) -> impl Iterator<Item = &'a str> {
     .filter(|s| !s.is_empty())
 }
 
-// @TODO-ZM: filter out common words
+// lowercases and folds the different spellings of the same word into one, eg:
+// "Développeur" -> "developpeur", "مُطَوِّر" -> "مطور", "إدارة" -> "اداره"
+pub fn normalize_text(s: &str) -> String {
+  let mut normalized = String::with_capacity(s.len());
+
+  // nfkd splits accented letters and arabic letters with hamza or madda into base letter + mark
+  for c in s.to_lowercase().nfkd() {
+    // drops latin accents and arabic tashkeel
+    if is_combining_mark(c) {
+      continue;
+    }
+
+    match c {
+      // tatweel
+      '\u{0640}' => {}
+      // alef wasla
+      '\u{0671}' => normalized.push('\u{0627}'),
+      // alef maksura
+      '\u{0649}' => normalized.push('\u{064A}'),
+      // ta marbuta
+      '\u{0629}' => normalized.push('\u{0647}'),
+      // arabic-indic and persian digits
+      '\u{0660}'..='\u{0669}' => normalized.push(char::from(b'0' + (c as u32 - 0x0660) as u8)),
+      '\u{06F0}'..='\u{06F9}' => normalized.push(char::from(b'0' + (c as u32 - 0x06F0) as u8)),
+      'œ' => normalized.push_str("oe"),
+      'æ' => normalized.push_str("ae"),
+      'ß' => normalized.push_str("ss"),
+      _ => normalized.push(c),
+    }
+  }
+
+  normalized
+}
+
 pub fn get_searchable_words(paragraph: &String) -> Vec<String> {
-  paragraph
-    .split(|c: char| !c.is_alphanumeric())
-    .into_iter()
-    .map(|s| s.to_string())
-    .filter(|s| !s.is_empty())
+  get_words(&normalize_text(paragraph))
+    .filter(|word| !is_stop_word(word))
+    .map(|word| word.to_string())
     .collect()
 }
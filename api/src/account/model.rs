@@ -29,6 +29,9 @@ pub struct Account {
   pub email: String,
   #[serde(flatten)]
   pub r#type: AccountType,
+  // trusted accounts skip moderation, only admins can grant it
+  #[serde(default)]
+  pub is_trusted: bool,
 }
 
 pub trait AccountTrait {
@@ -148,6 +151,7 @@ impl PartialAccountTrait for PartialAccount {
       slug: self.slug.clone().unwrap_or(fallback_account.slug),
       email: self.email.clone().unwrap_or(fallback_account.email),
       r#type: self.r#type.clone().unwrap_or(fallback_account.r#type),
+      is_trusted: self.is_trusted.unwrap_or(fallback_account.is_trusted),
     }
   }
 }
@@ -91,7 +91,7 @@ impl AccountRepository {
     // @TODO-ZM: use sqlx::query!
     let result = sqlx::query(
       r#"
-      SELECT id, email, slug, type, first_name, last_name, company_name, created_at
+      SELECT id, email, slug, type, first_name, last_name, company_name, is_trusted, created_at
       FROM account
       WHERE id = $1
       "#,
@@ -114,6 +114,7 @@ impl AccountRepository {
       "first_name": result.get::<String, _>("first_name"),
       "last_name": result.get::<String, _>("last_name"),
       "company_name": result.get::<String, _>("company_name"),
+      "is_trusted": result.get::<bool, _>("is_trusted"),
       "created_at": result.get::<String, _>("created_at"),
     });
 
@@ -138,7 +139,7 @@ impl AccountRepository {
     // @TODO-ZM: use sqlx::query!
     let result = sqlx::query(
       r#"
-      SELECT id, email, slug, type, first_name, last_name, company_name, created_at
+      SELECT id, email, slug, type, first_name, last_name, company_name, is_trusted, created_at
       FROM account
       WHERE email = $1
       "#,
@@ -168,6 +169,7 @@ impl AccountRepository {
       "first_name": result.get::<String, _>("first_name"),
       "last_name": result.get::<String, _>("last_name"),
       "company_name": result.get::<String, _>("company_name"),
+      "is_trusted": result.get::<bool, _>("is_trusted"),
       "created_at": result.get::<String, _>("created_at"),
     });
 
@@ -216,4 +218,40 @@ impl AccountRepository {
 
     Ok(id)
   }
+
+  pub async fn update_one_account_trust_by_id(
+    &self,
+    id: u32,
+    is_trusted: bool,
+  ) -> Result<(), DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      UPDATE account
+      SET is_trusted = $1
+      WHERE id = $2
+      "#,
+    )
+    .bind(is_trusted)
+    .bind(id)
+    .execute(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while updating one account trust: {:?}", db_result);
+      return Err(DataAccessError::InternalError);
+    }
+
+    if db_result.unwrap().rows_affected() == 0 {
+      return Err(DataAccessError::NotFound);
+    }
+
+    Ok(())
+  }
 }
@@ -0,0 +1,339 @@
+use axum::{
+  extract::{ConnectInfo, Path, Query, State},
+  response::IntoResponse,
+  Json, Router,
+};
+use hyper::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+use std::net::SocketAddr;
+
+use super::model::{ApplicationStatus, ApplicationStatusTrait, DBApplication};
+use crate::{
+  _entry::state::AppState,
+  _utils::{
+    error::{DataAccessError, SecurityError},
+    post_url::get_post_url,
+    query::{ApplicationListQuery, PaginationQuery, PaginationQueryTrait},
+    string::escape_html,
+  },
+  account::model::AccountTrait,
+  auth::service::ScopedToken,
+  post::model::PostTrait,
+  security::service::RateLimitConstraint,
+};
+
+const MAX_CANDIDATE_NAME_LENGTH: usize = 100;
+const MAX_CANDIDATE_EMAIL_LENGTH: usize = 254;
+const MAX_MESSAGE_LENGTH: usize = 5000;
+const MAX_LINK_COUNT: usize = 5;
+const MAX_LINK_LENGTH: usize = 2048;
+
+#[derive(Deserialize)]
+pub struct CreateOneApplicationBody {
+  candidate_name: String,
+  candidate_email: String,
+  message: String,
+  #[serde(default)]
+  links: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateOneApplicationBody {
+  status: ApplicationStatus,
+}
+
+// @TODO-ZM: verify the candidate's email like we do for posters
+fn is_valid_application(body: &CreateOneApplicationBody) -> bool {
+  let candidate_name = body.candidate_name.trim();
+  let candidate_email = body.candidate_email.trim();
+  let message = body.message.trim();
+
+  !candidate_name.is_empty()
+    && candidate_name.chars().count() <= MAX_CANDIDATE_NAME_LENGTH
+    && candidate_email.contains('@')
+    && candidate_email.len() <= MAX_CANDIDATE_EMAIL_LENGTH
+    && !message.is_empty()
+    && message.chars().count() <= MAX_MESSAGE_LENGTH
+    && body.links.len() <= MAX_LINK_COUNT
+    && body.links.iter().all(|link| {
+      (link.starts_with("https://") || link.starts_with("http://")) && link.len() <= MAX_LINK_LENGTH
+    })
+}
+
+pub async fn create_one_application(
+  ConnectInfo(ip): ConnectInfo<SocketAddr>,
+  State(app_state): State<AppState>,
+  Path(post_id): Path<u32>,
+  Json(body): Json<CreateOneApplicationBody>,
+) -> impl IntoResponse {
+  if !is_valid_application(&body) {
+    return StatusCode::BAD_REQUEST.into_response();
+  }
+  let candidate_email = body.candidate_email.trim().to_lowercase();
+
+  let post = app_state.post_repository.get_one_post_by_id(post_id).await;
+  if !post.is_ok() {
+    match post {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let post = post.unwrap();
+
+  // drafts, scheduled and closed posts don't take applications,
+  // checked first so an application to one of them doesn't use up the candidate's only one
+  if !post.is_live() {
+    return StatusCode::NOT_FOUND.into_response();
+  }
+
+  // @TODO-ZM: write a macro for this
+  match app_state.security_service.rate_limit(vec![
+    // one application per candidate and post
+    RateLimitConstraint {
+      id: format!("create_one_application-1-{}-{}", post_id, candidate_email),
+      max_requests: 1,
+      duration_ms: 86_400_000,
+    },
+    RateLimitConstraint {
+      id: format!("create_one_application-2-{}", ip.ip()),
+      max_requests: 10,
+      duration_ms: 3_600_000,
+    },
+  ]) {
+    Ok(_) => {}
+    Err(SecurityError::InternalError) => {
+      // @TODO-ZM: log error reason
+      return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    Err(SecurityError::RateLimitError) => {
+      return StatusCode::TOO_MANY_REQUESTS.into_response();
+    }
+  }
+
+  let poster = app_state
+    .account_repository
+    .get_one_account_by_id(post.poster_id)
+    .await;
+  if !poster.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let poster = poster.unwrap();
+
+  let application = DBApplication {
+    post_id,
+    candidate_name: body.candidate_name.trim().to_string(),
+    candidate_email,
+    message: body.message.trim().to_string(),
+    links: body.links.clone(),
+    status: ApplicationStatus::New,
+  };
+
+  let application_id = app_state
+    .application_repository
+    .create_one_application(&application)
+    .await;
+  if !application_id.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let application_id = application_id.unwrap();
+
+  let email_result = app_state
+    .email_service
+    .send_one_email(
+      &poster.email,
+      &"New application to your job post".to_string(),
+      &format!(
+        r#"You received a new application to your job post at dzjob.io with title:
+
+{}
+
+From: {} ({})
+
+{}
+
+{}
+
+You can review all the applications of your job post from your account at <a href="{}{}">dzjob.io</a>.
+
+Thank you for using our service!
+
+DZ Job Team
+contact@dzjob.io
+https://www.dzjob.io
+"#,
+        escape_html(&post.title),
+        escape_html(&application.candidate_name),
+        escape_html(&application.candidate_email),
+        escape_html(&application.message),
+        application
+          .links
+          .iter()
+          .map(|link| format!(r#"<a href="{}">{}</a>"#, escape_html(link), escape_html(link)))
+          .collect::<Vec<String>>()
+          .join("\n"),
+        app_state.config_service.get_config().web_base_url,
+        get_post_url(&post.to_compact_post(), &poster.to_compact_account()),
+      ),
+    )
+    .await;
+
+  // the application is already saved, the poster can still find it in their listing
+  if email_result.is_err() {
+    tracing::error!(
+      "Error while notifying the poster of application {}",
+      application_id
+    );
+  }
+
+  Json(json!({
+      "application_id": application_id,
+  }))
+  .into_response()
+}
+
+pub async fn get_many_applications(
+  State(app_state): State<AppState>,
+  scoped_token: ScopedToken,
+  Path(post_id): Path<u32>,
+  url_query: Query<ApplicationListQuery>,
+  pagination_query: Query<PaginationQuery>,
+) -> impl IntoResponse {
+  let post = app_state.post_repository.get_one_post_by_id(post_id).await;
+  if !post.is_ok() {
+    match post {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let post = post.unwrap();
+
+  if post.poster_id != scoped_token.id {
+    return StatusCode::FORBIDDEN.into_response();
+  }
+
+  let applications = app_state
+    .application_repository
+    .get_many_applications_by_post_id(post_id, &url_query.status, &pagination_query.to_db_query())
+    .await;
+  if !applications.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let applications = applications.unwrap();
+
+  Json(json!({
+      "applications": applications,
+  }))
+  .into_response()
+}
+
+pub async fn update_one_application_by_id(
+  State(app_state): State<AppState>,
+  scoped_token: ScopedToken,
+  Path((post_id, application_id)): Path<(u32, u32)>,
+  Json(body): Json<UpdateOneApplicationBody>,
+) -> impl IntoResponse {
+  let post = app_state.post_repository.get_one_post_by_id(post_id).await;
+  if !post.is_ok() {
+    match post {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let post = post.unwrap();
+
+  if post.poster_id != scoped_token.id {
+    return StatusCode::FORBIDDEN.into_response();
+  }
+
+  let application = app_state
+    .application_repository
+    .get_one_application_by_id(application_id)
+    .await;
+  if !application.is_ok() {
+    match application {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let application = application.unwrap();
+
+  if application.post_id != post.id {
+    return StatusCode::NOT_FOUND.into_response();
+  }
+
+  if !application.status.can_transition_to(&body.status) {
+    return StatusCode::CONFLICT.into_response();
+  }
+
+  let update_result = app_state
+    .application_repository
+    .update_one_application_status_by_id(application_id, &application.status, &body.status)
+    .await;
+  if !update_result.is_ok() {
+    match update_result {
+      // the status changed in the meantime
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::CONFLICT.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+
+  let application = app_state
+    .application_repository
+    .get_one_application_by_id(application_id)
+    .await;
+  if !application.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let application = application.unwrap();
+
+  Json(json!({
+      "application": application,
+  }))
+  .into_response()
+}
+
+pub fn create_application_router() -> Router<AppState> {
+  Router::new()
+    .route(
+      "/:post_id/applications",
+      axum::routing::post(create_one_application),
+    )
+    .route(
+      "/:post_id/applications",
+      axum::routing::get(get_many_applications),
+    )
+    .route(
+      "/:post_id/applications/:application_id",
+      axum::routing::patch(update_one_application_by_id),
+    )
+}
@@ -0,0 +1,3 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
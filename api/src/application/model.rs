@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+use utility_types::omit;
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ApplicationStatus {
+  New,
+  Reviewed,
+  Shortlisted,
+  Rejected,
+}
+
+pub trait ApplicationStatusTrait {
+  fn can_transition_to(&self, status: &ApplicationStatus) -> bool;
+}
+
+impl ApplicationStatusTrait for ApplicationStatus {
+  // applications only move forward, and a rejection is final
+  fn can_transition_to(&self, status: &ApplicationStatus) -> bool {
+    matches!(
+      (self, status),
+      (ApplicationStatus::New, ApplicationStatus::Reviewed)
+        | (ApplicationStatus::New, ApplicationStatus::Shortlisted)
+        | (ApplicationStatus::New, ApplicationStatus::Rejected)
+        | (ApplicationStatus::Reviewed, ApplicationStatus::Shortlisted)
+        | (ApplicationStatus::Reviewed, ApplicationStatus::Rejected)
+        | (ApplicationStatus::Shortlisted, ApplicationStatus::Rejected)
+    )
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[omit(DBApplication, [id, created_at, updated_at], [Debug, Serialize, Deserialize, Clone])]
+pub struct Application {
+  pub id: u32,
+  pub post_id: u32,
+  pub candidate_name: String,
+  pub candidate_email: String,
+  pub message: String,
+  pub links: Vec<String>,
+  pub status: ApplicationStatus,
+  pub created_at: String,
+  pub updated_at: String,
+}
@@ -0,0 +1,212 @@
+use serde_json::json;
+use sqlx::{sqlite::SqliteRow, Pool, QueryBuilder, Row, Sqlite};
+use std::sync::Arc;
+
+use super::model::{Application, ApplicationStatus, DBApplication};
+use crate::_utils::{error::DataAccessError, query::DBPaginationQuery};
+
+fn get_application_from_row(row: &SqliteRow) -> Result<Application, DataAccessError> {
+  let links = serde_json::from_str::<Vec<String>>(&row.get::<String, _>("links"));
+  if links.is_err() {
+    tracing::error!(
+      "Error while getting application, on parsing links, error: {:?}",
+      links.err()
+    );
+    return Err(DataAccessError::InternalError);
+  }
+
+  let json_application = json!({
+    "id": row.get::<u32, _>("id"),
+    "post_id": row.get::<u32, _>("post_id"),
+    "candidate_name": row.get::<String, _>("candidate_name"),
+    "candidate_email": row.get::<String, _>("candidate_email"),
+    "message": row.get::<String, _>("message"),
+    "links": links.unwrap(),
+    "status": row.get::<String, _>("status"),
+    "created_at": row.get::<String, _>("created_at"),
+    "updated_at": row.get::<String, _>("updated_at"),
+  });
+
+  let application = serde_json::from_value::<Application>(json_application);
+  if application.is_err() {
+    tracing::error!(
+      "Error while deserializing application: {:?}",
+      application.err()
+    );
+    return Err(DataAccessError::InternalError);
+  }
+
+  Ok(application.unwrap())
+}
+
+pub struct ApplicationRepository {
+  main_sql_db: Arc<Pool<Sqlite>>,
+}
+
+impl ApplicationRepository {
+  pub fn new(main_sql_db: Arc<Pool<Sqlite>>) -> Self {
+    Self { main_sql_db }
+  }
+
+  pub async fn create_one_application(
+    &self,
+    application: &DBApplication,
+  ) -> Result<u32, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let links = serde_json::to_string(&application.links);
+    if links.is_err() {
+      tracing::error!("Error while serializing application links: {:?}", links);
+      return Err(DataAccessError::InternalError);
+    }
+
+    let db_result = sqlx::query(
+      r#"
+      INSERT INTO application (post_id, candidate_name, candidate_email, message, links, status, created_at)
+      VALUES ($1, $2, $3, $4, $5, $6, strftime('%Y-%m-%dT%H:%M:%S.%fZ', 'now'))
+      "#,
+    )
+    .bind(application.post_id)
+    .bind(&application.candidate_name)
+    .bind(&application.candidate_email)
+    .bind(&application.message)
+    .bind(links.unwrap())
+    .bind(application.status.to_string())
+    .execute(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while creating one application: {:?}", db_result);
+      return Err(DataAccessError::InternalError);
+    }
+
+    let id = db_result.unwrap().last_insert_rowid() as u32;
+    Ok(id)
+  }
+
+  // newest first, optionally only the ones with the given status
+  pub async fn get_many_applications_by_post_id(
+    &self,
+    post_id: u32,
+    status: &Option<ApplicationStatus>,
+    pagination: &DBPaginationQuery,
+  ) -> Result<Vec<Application>, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let mut query_builder =
+      QueryBuilder::<Sqlite>::new("SELECT * FROM application WHERE post_id = ");
+    query_builder.push_bind(post_id);
+    if let Some(status) = status {
+      query_builder.push(" AND status = ");
+      query_builder.push_bind(status.to_string());
+    }
+    query_builder.push(" ORDER BY id DESC LIMIT ");
+    query_builder.push_bind(pagination.limit);
+    query_builder.push(" OFFSET ");
+    query_builder.push_bind(pagination.start);
+
+    let db_result = query_builder.build().fetch_all(&mut *conn).await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting many applications by post id: {:?}",
+        db_result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+    let db_result = db_result.unwrap();
+
+    let mut applications = vec![];
+    for row in db_result {
+      applications.push(get_application_from_row(&row)?);
+    }
+
+    Ok(applications)
+  }
+
+  pub async fn get_one_application_by_id(&self, id: u32) -> Result<Application, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT *
+      FROM application
+      WHERE id = $1
+      "#,
+    )
+    .bind(id)
+    .fetch_one(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      match db_result.err().unwrap() {
+        sqlx::Error::RowNotFound => {
+          return Err(DataAccessError::NotFound);
+        }
+        err => {
+          tracing::error!("Error while getting one application by id: {:?}", err);
+          return Err(DataAccessError::InternalError);
+        }
+      }
+    }
+
+    get_application_from_row(&db_result.unwrap())
+  }
+
+  // the current status is checked in the same query, so concurrent transitions can't both apply
+  pub async fn update_one_application_status_by_id(
+    &self,
+    id: u32,
+    from_status: &ApplicationStatus,
+    to_status: &ApplicationStatus,
+  ) -> Result<(), DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      UPDATE application
+      SET status = $1, updated_at = strftime('%Y-%m-%dT%H:%M:%S.%fZ', 'now')
+      WHERE id = $2 AND status = $3
+      "#,
+    )
+    .bind(to_status.to_string())
+    .bind(id)
+    .bind(from_status.to_string())
+    .execute(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while updating one application status: {:?}",
+        db_result
+      );
+      return Err(DataAccessError::InternalError);
+    }
+
+    if db_result.unwrap().rows_affected() == 0 {
+      return Err(DataAccessError::NotFound);
+    }
+
+    Ok(())
+  }
+}
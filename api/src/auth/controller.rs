@@ -12,6 +12,7 @@ use serde_json::json;
 use crate::{
   _entry::state::AppState,
   _utils::error::{DataAccessError, SecurityError},
+  account::model::AccountType,
   security::service::RateLimitConstraint,
 };
 
@@ -192,9 +193,14 @@ pub async fn confirm_login(
     return StatusCode::UNAUTHORIZED.into_response();
   }
 
+  let scope = match account.r#type {
+    AccountType::Admin { .. } => TokenScope::Admin,
+    _ => TokenScope::Login,
+  };
+
   let token = app_state
     .auth_service
-    .generate_scoped_token(TokenScope::Login, account.id)
+    .generate_scoped_token(scope, account.id)
     .await;
 
   if token.is_err() {
@@ -20,6 +20,8 @@ use crate::{_entry::state::AppState, _utils::error::AuthError, config::service::
 pub enum TokenScope {
   CreatePost,
   Login,
+  // a login by an admin account, required by the moderation endpoints
+  Admin,
 }
 
 const AUTH_TOKEN_EXPIRATION_MINUTES: i64 = 5;
@@ -31,6 +33,16 @@ pub struct ScopedToken {
   pub id: u32,
 }
 
+pub trait ScopedTokenTrait {
+  fn is_admin(&self) -> bool;
+}
+
+impl ScopedTokenTrait for ScopedToken {
+  fn is_admin(&self) -> bool {
+    matches!(self.scope, TokenScope::Admin)
+  }
+}
+
 // @TODO-ZM: move this into middleware?
 #[async_trait]
 impl FromRequestParts<AppState> for ScopedToken {
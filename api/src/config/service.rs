@@ -15,6 +15,19 @@ impl Stage {
   }
 }
 
+#[derive(Debug, Clone)]
+pub enum SearchBackendName {
+  Word,
+  Fts,
+}
+
+#[derive(Debug, Clone)]
+pub enum StemmingAlgorithm {
+  None,
+  Plural,
+  Light,
+}
+
 pub struct Config {
   pub stage: Stage,
   pub admin_auth_code: String,
@@ -23,8 +36,23 @@ pub struct Config {
   pub kv_db_dir: String,
   pub ai_service_auth_token: String,
   pub jwt_secret: String,
+  // keys the hashes of visitor ips, eg: for post stats and reports
+  pub ip_hash_secret: String,
   pub html_path: String,
   pub sqlite_base_url: String,
+  pub search_backend: SearchBackendName,
+  pub search_english_stemmer: StemmingAlgorithm,
+  pub search_french_stemmer: StemmingAlgorithm,
+  pub search_recency_half_life_days: f64,
+  pub api_base_url: String,
+  pub web_base_url: String,
+  pub post_expiry_days: i64,
+  pub post_max_expiry_days: i64,
+  pub post_expiry_reminder_days: i64,
+  // open reports needed to hide a post until an admin reviews it
+  pub post_report_threshold: u32,
+  // overrides the spam rules shipped with the binary
+  pub spam_rules_path: Option<String>,
 }
 
 pub struct ConfigService {}
@@ -64,9 +92,74 @@ impl ConfigService {
       ai_service_auth_token: std::env::var("AI_SERVICE_AUTH_TOKEN")
         .expect("AI_SERVICE_AUTH_TOKEN env variable is missing!"),
       jwt_secret: std::env::var("JWT_SECRET").expect("JWT_SECRET env variable is missing!"),
+      ip_hash_secret: std::env::var("IP_HASH_SECRET")
+        .expect("IP_HASH_SECRET env variable is missing!"),
       html_path: std::env::var("HTML_PATH").expect("HTML_PATH env variable is missing!"),
       sqlite_base_url: std::env::var("SQLITE_BASE_URL")
         .expect("SQLITE_BASE_URL env variable is missing!"),
+      search_backend: match std::env::var("SEARCH_BACKEND")
+        .unwrap_or("word".to_string())
+        .as_str()
+      {
+        "fts" => SearchBackendName::Fts,
+        _ => SearchBackendName::Word,
+      },
+      search_english_stemmer: match std::env::var("SEARCH_ENGLISH_STEMMER")
+        .unwrap_or("light".to_string())
+        .as_str()
+      {
+        "none" => StemmingAlgorithm::None,
+        "plural" => StemmingAlgorithm::Plural,
+        _ => StemmingAlgorithm::Light,
+      },
+      search_french_stemmer: match std::env::var("SEARCH_FRENCH_STEMMER")
+        .unwrap_or("light".to_string())
+        .as_str()
+      {
+        "none" => StemmingAlgorithm::None,
+        "plural" => StemmingAlgorithm::Plural,
+        _ => StemmingAlgorithm::Light,
+      },
+      search_recency_half_life_days: std::env::var("SEARCH_RECENCY_HALF_LIFE_DAYS")
+        .ok()
+        .and_then(|half_life| half_life.parse::<f64>().ok())
+        .filter(|half_life| *half_life > 0.0)
+        .unwrap_or(30.0),
+      api_base_url: match stage {
+        Stage::Development => "http://localhost:9090",
+        Stage::Staging => "https://staging.api.dzjob.io",
+        Stage::Production => "https://production.api.dzjob.io",
+      }
+      .to_string(),
+      web_base_url: match stage {
+        Stage::Development => "http://localhost:3000",
+        Stage::Staging => "https://staging.dzjob.io",
+        Stage::Production => "https://www.dzjob.io",
+      }
+      .to_string(),
+      post_expiry_days: std::env::var("POST_EXPIRY_DAYS")
+        .ok()
+        .and_then(|days| days.parse::<i64>().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(30),
+      post_max_expiry_days: std::env::var("POST_MAX_EXPIRY_DAYS")
+        .ok()
+        .and_then(|days| days.parse::<i64>().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(90),
+      post_expiry_reminder_days: std::env::var("POST_EXPIRY_REMINDER_DAYS")
+        .ok()
+        .and_then(|days| days.parse::<i64>().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(3),
+      post_report_threshold: std::env::var("POST_REPORT_THRESHOLD")
+        .ok()
+        .and_then(|threshold| threshold.parse::<u32>().ok())
+        .filter(|threshold| *threshold > 0)
+        .unwrap_or(5),
+      spam_rules_path: std::env::var("SPAM_RULES_PATH")
+        .ok()
+        .filter(|path| !path.is_empty()),
     }
   }
 }
@@ -1,10 +1,8 @@
+use serde_json::json;
 use std::sync::Arc;
 
 use crate::{
-  _utils::{
-    error::EmailError,
-    string::{escape_double_quote, escape_new_line, escape_new_line_with_br},
-  },
+  _utils::{error::EmailError, string::escape_new_line_with_br},
   config::service::{ConfigService, Stage},
 };
 
@@ -47,17 +45,13 @@ impl EmailService {
         "Authorization",
         self.config_service.get_config().email_service_auth_token,
       )
-      .body(format!(
-        r#"{{
-          "from": {{ "address": "noreply@dzjob.io", "name": "DZ Job" }},
-          "to": [{{ "email_address": {{ "address": "{}" }} }}],
-          "subject": "{}",
-          "htmlbody": "{}"
-        }}"#,
-        escape_new_line(&escape_double_quote(&email)),
-        escape_new_line(&escape_double_quote(&subject)),
-        escape_new_line_with_br(&escape_double_quote(&body))
-      ))
+      // serialized rather than formatted, so any character of the body is escaped
+      .json(&json!({
+        "from": { "address": "noreply@dzjob.io", "name": "DZ Job" },
+        "to": [{ "email_address": { "address": email } }],
+        "subject": subject,
+        "htmlbody": escape_new_line_with_br(body),
+      }))
       .send()
       .await;
 
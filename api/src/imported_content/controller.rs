@@ -43,6 +43,7 @@ pub async fn imported_content_status(
         title: "".to_string(),
         description: "".to_string(),
         poster: "".to_string(),
+        similar_post_id: None,
       });
 
       yield Event::default().data(json!({
@@ -50,6 +51,7 @@ pub async fn imported_content_status(
         "title": job_json_data.title,
         "description": job_json_data.description,
         "poster": job_json_data.poster,
+        "similar_post_id": job_json_data.similar_post_id,
       }).to_string());
 
 
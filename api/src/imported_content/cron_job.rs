@@ -2,6 +2,7 @@ use crate::{
   _entry::state::AppState,
   _utils::{database::DBOrderDirection, error::BootError},
   imported_content::model::ImportedContentStatus,
+  post::model::PostDuplicate,
 };
 use std::{
   sync::{
@@ -79,7 +80,39 @@ async fn run_importing_content_cron_job(app_state: AppState) {
       }
       break;
     }
-    let job_json_data = job_json_data.unwrap();
+    let mut job_json_data = job_json_data.unwrap();
+
+    // the poster isn't known yet, so only the recent posts are compared
+    let duplicate = app_state
+      .post_repository
+      .get_one_post_duplicate(None, None, &job_json_data.title, &job_json_data.description)
+      .await;
+    if duplicate.is_err() {
+      tracing::error!("Error while looking for a duplicate of imported_content");
+      return;
+    }
+    match duplicate.unwrap() {
+      Some(PostDuplicate::Exact { post_id }) => {
+        let imported_content_update_result = app_state
+          .imported_content_repository
+          .update_status_of_many_imported_contents_by_ids(
+            vec![id],
+            ImportedContentStatus::Failed {
+              failure_reason: format!("Duplicate of post {}", post_id),
+            },
+          )
+          .await;
+        if imported_content_update_result.is_err() {
+          tracing::error!("Error while updating imported_content");
+          return;
+        }
+        continue;
+      }
+      Some(PostDuplicate::Near { post_id }) => {
+        job_json_data.similar_post_id = Some(post_id);
+      }
+      None => {}
+    }
 
     let imported_content_update_result = app_state
       .imported_content_repository
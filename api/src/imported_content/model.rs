@@ -23,6 +23,9 @@ pub struct JobJsonData {
   pub title: String,
   pub description: String,
   pub poster: String,
+  // set when the imported post looks like an existing one, see get_one_post_duplicate
+  #[serde(default)]
+  pub similar_post_id: Option<u32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
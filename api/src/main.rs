@@ -2,13 +2,19 @@ mod _entry;
 mod _utils;
 mod account;
 mod ai;
+mod application;
 mod auth;
 mod config;
 mod email;
 mod imported_content;
+mod moderation;
 mod post;
+mod post_report;
+mod post_revision;
+mod post_stat;
 mod search;
 mod security;
+mod spam;
 mod tag;
 mod task;
 mod web;
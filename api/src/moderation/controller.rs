@@ -0,0 +1,526 @@
+use axum::{
+  extract::{Path, Query, State},
+  response::IntoResponse,
+  Json, Router,
+};
+use hyper::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+  _entry::state::AppState,
+  _utils::{
+    error::DataAccessError,
+    post_url::get_post_url,
+    query::{ModerationQueueQuery, PaginationQuery, PaginationQueryTrait},
+    string::escape_html,
+    vec::sort_and_dedup_vec,
+  },
+  account::model::AccountTrait,
+  auth::service::{ScopedToken, ScopedTokenTrait},
+  post::model::{Post, PostModerationStatus, PostTrait},
+  post_revision::model::{PostRevisionReason, PostSnapshotTrait},
+  task::model::{DBTask, TaskName, TaskStatus, TaskType},
+};
+
+const MAX_MODERATION_REASON_LENGTH: usize = 1000;
+
+#[derive(Deserialize)]
+pub struct ModerateOnePostBody {
+  status: PostModerationStatus,
+  // required when rejecting, it's sent to the poster
+  #[serde(default)]
+  reason: String,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateOneAccountTrustBody {
+  is_trusted: bool,
+}
+
+pub async fn get_moderation_queue(
+  State(app_state): State<AppState>,
+  scoped_token: ScopedToken,
+  url_query: Query<ModerationQueueQuery>,
+  pagination_query: Query<PaginationQuery>,
+) -> impl IntoResponse {
+  if !scoped_token.is_admin() {
+    return StatusCode::FORBIDDEN.into_response();
+  }
+
+  let moderation_status = url_query
+    .status
+    .clone()
+    .unwrap_or(PostModerationStatus::Pending);
+
+  let posts = app_state
+    .post_repository
+    .get_many_posts_by_moderation_status(&moderation_status, &pagination_query.to_db_query())
+    .await;
+  if !posts.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let posts = posts.unwrap();
+
+  let mut poster_ids = posts
+    .iter()
+    .map(|post| post.poster_id)
+    .collect::<Vec<u32>>();
+  sort_and_dedup_vec(&mut poster_ids);
+
+  let compact_posters = app_state
+    .account_repository
+    .get_many_compact_accounts_by_ids(poster_ids)
+    .await;
+  if !compact_posters.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let compact_posters = compact_posters.unwrap();
+
+  let spam_scores = app_state
+    .post_repository
+    .get_many_post_spam_scores_by_ids(posts.iter().map(|post| post.id).collect())
+    .await;
+  if !spam_scores.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let spam_scores = spam_scores.unwrap();
+
+  Json(json!({
+      "posts": posts,
+      "posters": compact_posters,
+      "spam_scores": spam_scores,
+  }))
+  .into_response()
+}
+
+pub async fn moderate_one_post_by_id(
+  State(app_state): State<AppState>,
+  scoped_token: ScopedToken,
+  Path(post_id): Path<u32>,
+  Json(body): Json<ModerateOnePostBody>,
+) -> impl IntoResponse {
+  if !scoped_token.is_admin() {
+    return StatusCode::FORBIDDEN.into_response();
+  }
+
+  let moderation_reason = body.reason.trim().to_string();
+  let is_valid = match body.status {
+    PostModerationStatus::Pending => false,
+    PostModerationStatus::Approved => true,
+    PostModerationStatus::Rejected => {
+      !moderation_reason.is_empty()
+        && moderation_reason.chars().count() <= MAX_MODERATION_REASON_LENGTH
+    }
+  };
+  if !is_valid {
+    return StatusCode::BAD_REQUEST.into_response();
+  }
+  // a reason only makes sense for a rejection
+  let moderation_reason = match body.status {
+    PostModerationStatus::Rejected => moderation_reason,
+    _ => "".to_string(),
+  };
+
+  let post = app_state.post_repository.get_one_post_by_id(post_id).await;
+  if !post.is_ok() {
+    match post {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let post = post.unwrap();
+
+  // unconfirmed posts aren't in the queue yet, and a decision is only taken once
+  if !post.is_published || post.moderation_status == body.status {
+    return StatusCode::CONFLICT.into_response();
+  }
+  let was_live = post.is_live();
+
+  let moderated_post = Post {
+    moderation_status: body.status.clone(),
+    moderation_reason: moderation_reason.clone(),
+    ..post
+  };
+
+  let moderation_result = app_state
+    .post_repository
+    .moderate_one_post_by_id(
+      post_id,
+      &body.status,
+      &moderation_reason,
+      &moderated_post.to_db_post_revision(scoped_token.id, PostRevisionReason::Moderated),
+    )
+    .await;
+  if !moderation_result.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+
+  // the decision covers the reports received so far
+  let review_result = app_state
+    .post_report_repository
+    .review_many_post_reports_by_post_id(post_id)
+    .await;
+  if !review_result.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+
+  let post = app_state.post_repository.get_one_post_by_id(post_id).await;
+  if !post.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let post = post.unwrap();
+
+  // approved posts are indexed by PostCronJob once their publishing date is reached
+  let task_name = match (&post.moderation_status, was_live) {
+    (PostModerationStatus::Approved, _) => Some(TaskName::Publishing {
+      model_name: "post".to_string(),
+      model_id: post.id,
+    }),
+    (PostModerationStatus::Rejected, true) => Some(TaskName::UndoIndexing {
+      model_name: "post".to_string(),
+      model_id: post.id,
+    }),
+    _ => None,
+  };
+  if let Some(name) = task_name {
+    let task_id = app_state
+      .task_repository
+      .create_one_task(DBTask {
+        name,
+        status: TaskStatus::Pending,
+        r#type: TaskType::Automated,
+      })
+      .await;
+    if !task_id.is_ok() {
+      // @TODO-ZM: log error reason
+      return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+  }
+
+  let poster = app_state
+    .account_repository
+    .get_one_account_by_id(post.poster_id)
+    .await;
+  if !poster.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let poster = poster.unwrap();
+
+  let (subject, decision) = match post.moderation_status {
+    PostModerationStatus::Rejected => (
+      "Your job post was not approved",
+      format!(
+        "was not approved, for the following reason:\n\n{}",
+        escape_html(&post.moderation_reason)
+      ),
+    ),
+    _ => (
+      "Your job post was approved",
+      format!(
+        r#"was approved, and will be visible at <a href="{}{}">dzjob.io</a>."#,
+        app_state.config_service.get_config().web_base_url,
+        get_post_url(&post.to_compact_post(), &poster.to_compact_account()),
+      ),
+    ),
+  };
+
+  let email_result = app_state
+    .email_service
+    .send_one_email(
+      &poster.email,
+      &subject.to_string(),
+      &format!(
+        r#"Your job post at dzjob.io with title:
+
+{}
+
+{}
+
+If you have any question, reply to contact@dzjob.io.
+
+Thank you for using our service!
+
+DZ Job Team
+contact@dzjob.io
+https://www.dzjob.io
+"#,
+        escape_html(&post.title),
+        decision,
+      ),
+    )
+    .await;
+
+  // the decision is already saved, the poster can still see it on their post
+  if email_result.is_err() {
+    tracing::error!(
+      "Error while notifying the poster of the moderation of post {}",
+      post.id
+    );
+  }
+
+  Json(json!({
+      "post": post,
+  }))
+  .into_response()
+}
+
+pub async fn update_one_account_trust_by_id(
+  State(app_state): State<AppState>,
+  scoped_token: ScopedToken,
+  Path(account_id): Path<u32>,
+  Json(body): Json<UpdateOneAccountTrustBody>,
+) -> impl IntoResponse {
+  if !scoped_token.is_admin() {
+    return StatusCode::FORBIDDEN.into_response();
+  }
+
+  let update_result = app_state
+    .account_repository
+    .update_one_account_trust_by_id(account_id, body.is_trusted)
+    .await;
+  if !update_result.is_ok() {
+    match update_result {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+
+  let account = app_state
+    .account_repository
+    .get_one_account_by_id(account_id)
+    .await;
+  if !account.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let account = account.unwrap();
+
+  Json(json!({
+      "account": account,
+  }))
+  .into_response()
+}
+
+pub async fn get_many_reported_posts(
+  State(app_state): State<AppState>,
+  scoped_token: ScopedToken,
+  pagination_query: Query<PaginationQuery>,
+) -> impl IntoResponse {
+  if !scoped_token.is_admin() {
+    return StatusCode::FORBIDDEN.into_response();
+  }
+
+  let reported_posts = app_state
+    .post_report_repository
+    .get_many_reported_posts(&pagination_query.to_db_query())
+    .await;
+  if !reported_posts.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let reported_posts = reported_posts.unwrap();
+
+  let posts = app_state
+    .post_repository
+    .get_many_posts_by_ids(
+      reported_posts
+        .iter()
+        .map(|reported_post| reported_post.post_id)
+        .collect(),
+    )
+    .await;
+  if !posts.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let posts = posts.unwrap();
+
+  let mut poster_ids = posts
+    .iter()
+    .map(|post| post.poster_id)
+    .collect::<Vec<u32>>();
+  sort_and_dedup_vec(&mut poster_ids);
+
+  let compact_posters = app_state
+    .account_repository
+    .get_many_compact_accounts_by_ids(poster_ids)
+    .await;
+  if !compact_posters.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let compact_posters = compact_posters.unwrap();
+
+  Json(json!({
+      "reported_posts": reported_posts,
+      "posts": posts,
+      "posters": compact_posters,
+  }))
+  .into_response()
+}
+
+pub async fn get_many_post_reports(
+  State(app_state): State<AppState>,
+  scoped_token: ScopedToken,
+  Path(post_id): Path<u32>,
+  pagination_query: Query<PaginationQuery>,
+) -> impl IntoResponse {
+  if !scoped_token.is_admin() {
+    return StatusCode::FORBIDDEN.into_response();
+  }
+
+  let post_reports = app_state
+    .post_report_repository
+    .get_many_post_reports_by_post_id(post_id, &pagination_query.to_db_query())
+    .await;
+  if !post_reports.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let post_reports = post_reports.unwrap();
+
+  Json(json!({
+      "post_reports": post_reports,
+  }))
+  .into_response()
+}
+
+// dismisses the open reports of a post, a post hidden by them is approved again
+pub async fn review_many_post_reports(
+  State(app_state): State<AppState>,
+  scoped_token: ScopedToken,
+  Path(post_id): Path<u32>,
+) -> impl IntoResponse {
+  if !scoped_token.is_admin() {
+    return StatusCode::FORBIDDEN.into_response();
+  }
+
+  let post = app_state.post_repository.get_one_post_by_id(post_id).await;
+  if !post.is_ok() {
+    match post {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let post = post.unwrap();
+
+  let open_report_count = app_state
+    .post_report_repository
+    .get_open_post_report_count_by_post_id(post_id)
+    .await;
+  if !open_report_count.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let open_report_count = open_report_count.unwrap();
+
+  // only live posts can be reported, so a pending one with enough open reports was hidden by them
+  let is_hidden_by_reports = post.moderation_status == PostModerationStatus::Pending
+    && open_report_count >= app_state.config_service.get_config().post_report_threshold;
+
+  let review_result = app_state
+    .post_report_repository
+    .review_many_post_reports_by_post_id(post_id)
+    .await;
+  if !review_result.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+
+  if !is_hidden_by_reports {
+    return StatusCode::NO_CONTENT.into_response();
+  }
+
+  let approved_post = Post {
+    moderation_status: PostModerationStatus::Approved,
+    moderation_reason: "".to_string(),
+    ..post
+  };
+
+  let moderation_result = app_state
+    .post_repository
+    .moderate_one_post_by_id(
+      post_id,
+      &approved_post.moderation_status,
+      &approved_post.moderation_reason,
+      &approved_post.to_db_post_revision(scoped_token.id, PostRevisionReason::Moderated),
+    )
+    .await;
+  if !moderation_result.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+
+  let post = app_state.post_repository.get_one_post_by_id(post_id).await;
+  if !post.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let post = post.unwrap();
+
+  // it might have been archived while hidden
+  if post.is_live() {
+    let task_id = app_state
+      .task_repository
+      .create_one_task(DBTask {
+        name: TaskName::Indexing {
+          model_name: "post".to_string(),
+          model_id: post.id,
+        },
+        status: TaskStatus::Pending,
+        r#type: TaskType::Automated,
+      })
+      .await;
+    if !task_id.is_ok() {
+      // @TODO-ZM: log error reason
+      return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+  }
+
+  StatusCode::NO_CONTENT.into_response()
+}
+
+pub fn create_moderation_router() -> Router<AppState> {
+  Router::new()
+    .route("/posts", axum::routing::get(get_moderation_queue))
+    .route(
+      "/posts/:post_id",
+      axum::routing::patch(moderate_one_post_by_id),
+    )
+    .route(
+      "/posts/:post_id/reports",
+      axum::routing::get(get_many_post_reports),
+    )
+    .route(
+      "/posts/:post_id/reports/review",
+      axum::routing::post(review_many_post_reports),
+    )
+    .route("/reports", axum::routing::get(get_many_reported_posts))
+    .route(
+      "/accounts/:account_id",
+      axum::routing::patch(update_one_account_trust_by_id),
+    )
+}
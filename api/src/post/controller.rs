@@ -1,6 +1,6 @@
 use axum::{
-  extract::{ConnectInfo, Path, State},
-  response::IntoResponse,
+  extract::{ConnectInfo, Path, Query, State},
+  response::{IntoResponse, Redirect},
   Json, Router,
 };
 use hyper::StatusCode;
@@ -9,31 +9,192 @@ use serde::Deserialize;
 use serde_json::json;
 use std::net::SocketAddr;
 
-use super::model::DBPost;
+use super::model::{
+  get_facet_tag_ids, parse_post_feed_cursor, CompactPostTrait, DBPost, PartialPost,
+  PartialPostTrait, Post, PostAttributes, PostAttributesTrait, PostDuplicate, PostFacets,
+  PostFeedFilters, PostModerationStatus, PostTrait,
+};
 use crate::{
   _entry::state::AppState,
   _utils::{
-    database::DBOrderDirection,
+    date::{format_date, parse_date},
     error::{DataAccessError, SecurityError},
+    markdown::{get_markdown_text, render_markdown},
+    post_url::get_post_url,
+    query::{
+      split_comma_separated_values, DBPaginationQuery, ExtendPostQuery, FacetQuery, FeedQuery,
+      PaginationQuery, PaginationQueryTrait, PostAttributeQuery, PostAttributeQueryTrait,
+    },
     string::slugify,
     vec::sort_and_dedup_vec,
   },
   account::model::{AccountNameTrait, DBAccount},
-  auth::service::{ScopedToken, TokenScope},
+  auth::service::{ScopedToken, ScopedTokenTrait, TokenScope},
+  config::service::Config,
+  post_revision::model::{PostRevisionReason, PostSnapshotTrait},
+  post_stat::{controller::record_one_post_event, model::PostEventKind},
+  search::model::{SearchFilters, SearchSort},
   security::service::RateLimitConstraint,
+  spam::model::SpamVerdict,
   task::model::{DBTask, TaskName, TaskStatus, TaskType},
 };
 
-pub async fn get_all_posts_for_feed(State(app_state): State<AppState>) -> impl IntoResponse {
+const SIMILAR_POST_COUNT: u32 = 20;
+const TAG_POST_COUNT: u32 = 100;
+const MAX_SCHEDULING_DAYS: i64 = 90;
+
+// an empty expires_at falls back to the default window, starting when the post is published
+fn get_expires_at(
+  requested_expires_at: &String,
+  published_at: &chrono::DateTime<chrono::Utc>,
+  config: &Config,
+) -> Option<String> {
+  if requested_expires_at.is_empty() {
+    return Some(format_date(
+      &(*published_at + chrono::Duration::days(config.post_expiry_days)),
+    ));
+  }
+
+  let expires_at = parse_date(requested_expires_at)?;
+  if expires_at <= *published_at
+    || expires_at > *published_at + chrono::Duration::days(config.post_max_expiry_days)
+  {
+    return None;
+  }
+
+  Some(format_date(&expires_at))
+}
+
+// @TODO-ZM: summarize description using AI
+// posts from trusted accounts skip the moderation queue, unless they look like another post or like spam
+fn get_initial_moderation_status(
+  is_poster_trusted: bool,
+  similar_post_id: &Option<u32>,
+  spam_verdict: &SpamVerdict,
+) -> PostModerationStatus {
+  match is_poster_trusted && similar_post_id.is_none() && *spam_verdict == SpamVerdict::Allow {
+    true => PostModerationStatus::Approved,
+    false => PostModerationStatus::Pending,
+  }
+}
+
+// edits go back to the moderation queue like new posts do, otherwise the post keeps its status
+pub fn get_edited_moderation_status(
+  is_poster_trusted: bool,
+  similar_post_id: &Option<u32>,
+  spam_verdict: &SpamVerdict,
+) -> Option<PostModerationStatus> {
+  match is_poster_trusted && similar_post_id.is_none() && *spam_verdict == SpamVerdict::Allow {
+    true => None,
+    false => Some(PostModerationStatus::Pending),
+  }
+}
+
+// pending and rejected posts are only shown to their poster and to admins
+fn is_post_visible(post: &Post, scoped_token: &Option<ScopedToken>) -> bool {
+  post.moderation_status == PostModerationStatus::Approved
+    || scoped_token
+      .as_ref()
+      .is_some_and(|scoped_token| scoped_token.id == post.poster_id || scoped_token.is_admin())
+}
+
+fn get_short_description(description: &String) -> String {
+  get_markdown_text(description)
+    .split_whitespace()
+    .take(20)
+    .collect::<Vec<&str>>()
+    .join(" ")
+}
+
+pub async fn get_all_posts_for_feed(
+  State(app_state): State<AppState>,
+  url_query: Query<FeedQuery>,
+  attribute_query: Query<PostAttributeQuery>,
+  facet_query: Query<FacetQuery>,
+  pagination_query: Query<PaginationQuery>,
+) -> impl IntoResponse {
+  let cursor = match &url_query.cursor {
+    Some(cursor) => {
+      let cursor = parse_post_feed_cursor(cursor);
+      if cursor.is_none() {
+        return StatusCode::BAD_REQUEST.into_response();
+      }
+      cursor
+    }
+    None => None,
+  };
+
+  let attribute_filters = attribute_query.to_filters();
+  if attribute_filters.is_none() {
+    return StatusCode::BAD_REQUEST.into_response();
+  }
+  let attribute_filters = attribute_filters.unwrap();
+
+  // a repeated tag would otherwise be counted as an unknown one below
+  let mut tag_slugs = split_comma_separated_values(&url_query.tags);
+  sort_and_dedup_vec(&mut tag_slugs);
+
+  let filter_tags = app_state
+    .tag_repository
+    .get_many_compact_tags_by_slugs(&tag_slugs)
+    .await;
+  if filter_tags.is_err() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let filter_tags = filter_tags.unwrap();
+
+  // an unknown tag can't match any post
+  if filter_tags.len() < tag_slugs.len() {
+    return Json(json!({
+        "posts": [],
+        "tags": [],
+        "posters": [],
+        "next_cursor": null,
+        "facets": match facet_query.facets {
+          true => Some(PostFacets::default()),
+          false => None,
+        },
+    }))
+    .into_response();
+  }
+
+  let filters = PostFeedFilters {
+    tag_ids: filter_tags.iter().map(|tag| tag.id).collect(),
+    poster_id: url_query.poster_id,
+    attributes: attribute_filters,
+  };
+  let pagination = pagination_query.to_db_query();
+
+  let facets = match facet_query.facets {
+    true => {
+      let facets = app_state
+        .post_repository
+        .get_published_post_facets(&filters)
+        .await;
+      if facets.is_err() {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+      Some(facets.unwrap())
+    }
+    false => None,
+  };
+
   let compact_posts = app_state
     .post_repository
-    .get_many_published_compact_posts("published_at", DBOrderDirection::DESC, 20, 0)
+    .get_many_compact_posts_for_feed(&filters, &cursor, pagination.limit, pagination.start)
     .await;
   if !compact_posts.is_ok() {
     return StatusCode::INTERNAL_SERVER_ERROR.into_response();
   }
   let compact_posts = compact_posts.unwrap();
 
+  let next_cursor = match compact_posts.len() as u32 == pagination.limit {
+    true => compact_posts.last().map(|post| post.to_feed_cursor()),
+    false => None,
+  };
+
   let mut unique_tag_ids: Vec<u32> = Vec::new();
   let mut unique_poster_ids: Vec<u32> = Vec::new();
 
@@ -42,6 +203,11 @@ pub async fn get_all_posts_for_feed(State(app_state): State<AppState>) -> impl I
     unique_poster_ids.push(post.poster_id);
   }
 
+  // the counted tags are returned along the posts' ones
+  if let Some(facets) = &facets {
+    unique_tag_ids.append(&mut get_facet_tag_ids(facets));
+  }
+
   sort_and_dedup_vec(&mut unique_tag_ids);
   sort_and_dedup_vec(&mut unique_poster_ids);
 
@@ -69,12 +235,16 @@ pub async fn get_all_posts_for_feed(State(app_state): State<AppState>) -> impl I
       "posts": compact_posts,
       "tags": compact_tags,
       "posters": compact_posters,
+      "next_cursor": next_cursor,
+      "facets": facets,
   }))
   .into_response()
 }
 
 pub async fn get_one_post_by_id(
+  ConnectInfo(ip): ConnectInfo<SocketAddr>,
   State(app_state): State<AppState>,
+  scoped_token: Option<ScopedToken>,
   Path(id): Path<u32>,
 ) -> impl IntoResponse {
   let post = app_state.post_repository.get_one_post_by_id(id).await;
@@ -92,6 +262,10 @@ pub async fn get_one_post_by_id(
   }
   let post = post.unwrap();
 
+  if !is_post_visible(&post, &scoped_token) {
+    return StatusCode::NOT_FOUND.into_response();
+  }
+
   let compact_tags = app_state
     .tag_repository
     .get_many_compact_tags_by_ids(&post.tag_ids)
@@ -112,6 +286,10 @@ pub async fn get_one_post_by_id(
   }
   let poster = poster.unwrap();
 
+  if post.is_live() {
+    record_one_post_event(&app_state, post.id, PostEventKind::View, &ip).await;
+  }
+
   Json(json!({
       "post": post,
       "tags": compact_tags,
@@ -122,6 +300,7 @@ pub async fn get_one_post_by_id(
 
 pub async fn get_many_similar_posts_by_id(
   State(app_state): State<AppState>,
+  scoped_token: Option<ScopedToken>,
   Path(id): Path<u32>,
 ) -> impl IntoResponse {
   let post = app_state.post_repository.get_one_post_by_id(id).await;
@@ -131,6 +310,11 @@ pub async fn get_many_similar_posts_by_id(
   }
   let post = post.unwrap();
 
+  // archived posts still resolve, but no longer lead to other posts
+  if !is_post_visible(&post, &scoped_token) || post.is_archived {
+    return StatusCode::NOT_FOUND.into_response();
+  }
+
   let poster = app_state
     .account_repository
     .get_one_account_by_id(post.poster_id)
@@ -151,28 +335,41 @@ pub async fn get_many_similar_posts_by_id(
   }
   let tags = tags.unwrap();
 
-  let post_ids = app_state
+  let search_result = app_state
     .search_service
-    .search_posts(&format!(
-      "{} {} {}",
-      post.title,
-      poster.get_display_name(),
-      tags
-        .iter()
-        .map(|tag| tag.name.clone())
-        .collect::<Vec<String>>()
-        .join(" ")
-    ))
+    .search_posts(
+      &format!(
+        "{} {} {}",
+        post.title,
+        poster.get_display_name(),
+        tags
+          .iter()
+          .map(|tag| tag.name.clone())
+          .collect::<Vec<String>>()
+          .join(" ")
+      ),
+      &SearchFilters::default(),
+      &SearchSort::Relevance,
+      &None,
+      // one more than needed, as the post itself is usually the top result
+      &DBPaginationQuery {
+        limit: SIMILAR_POST_COUNT + 1,
+        start: 0,
+      },
+    )
     .await;
 
-  if !post_ids.is_ok() {
+  if !search_result.is_ok() {
     // @TODO-ZM: log error reason
     return StatusCode::INTERNAL_SERVER_ERROR.into_response();
   }
-  let post_ids = post_ids.unwrap();
-  let post_ids = post_ids
+  let search_result = search_result.unwrap();
+  let post_ids = search_result
+    .records
     .into_iter()
+    .map(|record| record.id)
     .filter(|&id| id != post.id)
+    .take(SIMILAR_POST_COUNT as usize)
     .collect::<Vec<u32>>();
 
   let similar_compact_posts = app_state
@@ -261,13 +458,30 @@ pub async fn get_many_compact_posts_for_tag(
   }
   let tag = tag.unwrap();
 
-  let post_ids = app_state.search_service.search_posts(&tag.name).await;
+  let search_result = app_state
+    .search_service
+    .search_posts(
+      &tag.name,
+      &SearchFilters::default(),
+      &SearchSort::Relevance,
+      &None,
+      &DBPaginationQuery {
+        limit: TAG_POST_COUNT,
+        start: 0,
+      },
+    )
+    .await;
 
-  if !post_ids.is_ok() {
+  if !search_result.is_ok() {
     // @TODO-ZM: log error reason
     return StatusCode::INTERNAL_SERVER_ERROR.into_response();
   }
-  let post_ids = post_ids.unwrap();
+  let post_ids = search_result
+    .unwrap()
+    .records
+    .into_iter()
+    .map(|record| record.id)
+    .collect::<Vec<u32>>();
 
   let compact_posts = app_state
     .post_repository
@@ -362,7 +576,23 @@ pub async fn create_one_post_with_poster(
     }
   }
 
+  let spam_score = app_state.spam_service.get_post_spam_score(
+    &body.post.title,
+    &body.post.description,
+    &body.poster.email,
+  );
+  if spam_score.verdict == SpamVerdict::Reject {
+    return (
+      StatusCode::UNPROCESSABLE_ENTITY,
+      Json(json!({
+          "spam_rules": spam_score.matched_rules,
+      })),
+    )
+      .into_response();
+  }
+
   let poster_id;
+  let is_poster_trusted;
 
   let existing_poster = app_state
     .account_repository
@@ -386,6 +616,7 @@ pub async fn create_one_post_with_poster(
         }
 
         poster_id = poster_id_result.unwrap();
+        is_poster_trusted = false;
       }
       _ => {
         // @TODO-ZM: log error reason
@@ -393,9 +624,52 @@ pub async fn create_one_post_with_poster(
       }
     }
   } else {
-    poster_id = existing_poster.unwrap().id;
+    let existing_poster = existing_poster.unwrap();
+    poster_id = existing_poster.id;
+    is_poster_trusted = existing_poster.is_trusted;
   }
 
+  if !body.post.attributes.is_valid() {
+    return StatusCode::BAD_REQUEST.into_response();
+  }
+
+  let duplicate = app_state
+    .post_repository
+    .get_one_post_duplicate(
+      None,
+      Some(poster_id),
+      &body.post.title,
+      &body.post.description,
+    )
+    .await;
+  if !duplicate.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let similar_post_id = match duplicate.unwrap() {
+    Some(PostDuplicate::Exact { post_id }) => {
+      return (
+        StatusCode::CONFLICT,
+        Json(json!({
+            "duplicate_post_id": post_id,
+        })),
+      )
+        .into_response();
+    }
+    Some(PostDuplicate::Near { post_id }) => Some(post_id),
+    None => None,
+  };
+
+  let expires_at = get_expires_at(
+    &body.post.expires_at,
+    &chrono::Utc::now(),
+    &app_state.config_service.get_config(),
+  );
+  if expires_at.is_none() {
+    return StatusCode::BAD_REQUEST.into_response();
+  }
+  let expires_at = expires_at.unwrap();
+
   let compact_tags = app_state
     .tag_repository
     .get_many_compact_tags_by_ids(&body.post.tag_ids)
@@ -406,23 +680,29 @@ pub async fn create_one_post_with_poster(
   }
   let compact_tags = compact_tags.unwrap();
 
+  let db_post = DBPost {
+    poster_id,
+    slug: slugify(&body.post.title),
+    is_published: false,
+    expires_at,
+    moderation_status: get_initial_moderation_status(
+      is_poster_trusted,
+      &similar_post_id,
+      &spam_score.verdict,
+    ),
+    moderation_reason: "".to_string(),
+    similar_post_id,
+    short_description: get_short_description(&body.post.description),
+    tag_ids: compact_tags.iter().map(|tag| tag.id).collect::<Vec<u32>>(),
+    ..body.post.clone()
+  };
   let post_id = app_state
     .post_repository
-    .create_one_post(&DBPost {
-      poster_id,
-      slug: slugify(&body.post.title),
-      is_published: false,
-      // @TODO-ZM: summarize description using AI
-      short_description: body
-        .post
-        .description
-        .split_whitespace()
-        .take(20)
-        .collect::<Vec<&str>>()
-        .join(" "),
-      tag_ids: compact_tags.iter().map(|tag| tag.id).collect::<Vec<u32>>(),
-      ..body.post.clone()
-    })
+    .create_one_post(
+      &db_post,
+      &spam_score,
+      &db_post.to_db_post_revision(poster_id, PostRevisionReason::Created),
+    )
     .await;
 
   if !post_id.is_ok() {
@@ -518,6 +798,52 @@ pub async fn confirm_post(
     }
   }
 
+  let post = app_state
+    .post_repository
+    .get_one_post_by_id(body.post_id)
+    .await;
+  if !post.is_ok() {
+    match post {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let post = post.unwrap();
+
+  // the same post might have been posted and confirmed since this one was created,
+  // the code is kept until the post can be confirmed
+  let duplicate = app_state
+    .post_repository
+    .get_one_post_duplicate(
+      Some(post.id),
+      Some(post.poster_id),
+      &post.title,
+      &post.description,
+    )
+    .await;
+  if !duplicate.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let similar_post_id = match duplicate.unwrap() {
+    Some(PostDuplicate::Exact { post_id }) => {
+      return (
+        StatusCode::CONFLICT,
+        Json(json!({
+            "duplicate_post_id": post_id,
+        })),
+      )
+        .into_response();
+    }
+    Some(PostDuplicate::Near { post_id }) => Some(post_id),
+    None => None,
+  };
+
   let kv_db_result = app_state.main_kv_db.compare_and_swap(
     body.post_id.to_be_bytes(),
     Some(format!("{}{}", body.confirmation_id, body.confirmation_code).as_bytes()),
@@ -529,39 +855,64 @@ pub async fn confirm_post(
     return StatusCode::UNAUTHORIZED.into_response();
   }
 
-  let update_result = app_state
-    .post_repository
-    .publish_one_post_by_id(body.post_id)
-    .await;
-  if !update_result.is_ok() {
-    // @TODO-ZM: log error reason
-    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-  }
+  // a near duplicate found on confirmation waits for moderation like one found on creation
+  let post = Post {
+    published_at: format_date(&chrono::Utc::now()),
+    is_published: true,
+    moderation_status: match similar_post_id {
+      Some(_) => PostModerationStatus::Pending,
+      None => post.moderation_status,
+    },
+    similar_post_id: similar_post_id.or(post.similar_post_id),
+    ..post
+  };
 
-  let post = app_state
+  let publish_result = app_state
     .post_repository
-    .get_one_post_by_id(body.post_id)
+    .publish_one_post(
+      &post,
+      &post.to_db_post_revision(post.poster_id, PostRevisionReason::Published),
+    )
     .await;
-  if !post.is_ok() {
+  if !publish_result.is_ok() {
     // @TODO-ZM: log error reason
     return StatusCode::INTERNAL_SERVER_ERROR.into_response();
   }
-  let post = post.unwrap();
 
-  let task_id = app_state
-    .task_repository
-    .create_one_task(DBTask {
-      name: TaskName::Indexing {
-        model_name: "post".to_string(),
-        model_id: post.id,
-      },
-      status: TaskStatus::Pending,
-      r#type: TaskType::Automated,
-    })
-    .await;
-  if !task_id.is_ok() {
-    // @TODO-ZM: log error reason
-    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  // pending posts are indexed once approved, see the moderation controller
+  if post.is_live() {
+    let task_id = app_state
+      .task_repository
+      .create_one_task(DBTask {
+        name: TaskName::Indexing {
+          model_name: "post".to_string(),
+          model_id: post.id,
+        },
+        status: TaskStatus::Pending,
+        r#type: TaskType::Automated,
+      })
+      .await;
+    if !task_id.is_ok() {
+      // @TODO-ZM: log error reason
+      return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+
+    // the poster becomes searchable with their first published post
+    let task_id = app_state
+      .task_repository
+      .create_one_task(DBTask {
+        name: TaskName::Indexing {
+          model_name: "account".to_string(),
+          model_id: post.poster_id,
+        },
+        status: TaskStatus::Pending,
+        r#type: TaskType::Automated,
+      })
+      .await;
+    if !task_id.is_ok() {
+      // @TODO-ZM: log error reason
+      return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
   }
 
   let poster = app_state
@@ -655,6 +1006,76 @@ pub async fn create_one_post(
     }
   }
 
+  if !body.post.attributes.is_valid() {
+    return StatusCode::BAD_REQUEST.into_response();
+  }
+
+  let spam_score = app_state.spam_service.get_post_spam_score(
+    &body.post.title,
+    &body.post.description,
+    &poster.email,
+  );
+  if spam_score.verdict == SpamVerdict::Reject {
+    return (
+      StatusCode::UNPROCESSABLE_ENTITY,
+      Json(json!({
+          "spam_rules": spam_score.matched_rules,
+      })),
+    )
+      .into_response();
+  }
+
+  let duplicate = app_state
+    .post_repository
+    .get_one_post_duplicate(
+      None,
+      Some(poster.id),
+      &body.post.title,
+      &body.post.description,
+    )
+    .await;
+  if !duplicate.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let similar_post_id = match duplicate.unwrap() {
+    Some(PostDuplicate::Exact { post_id }) => {
+      return (
+        StatusCode::CONFLICT,
+        Json(json!({
+            "duplicate_post_id": post_id,
+        })),
+      )
+        .into_response();
+    }
+    Some(PostDuplicate::Near { post_id }) => Some(post_id),
+    None => None,
+  };
+
+  // a future published_at schedules the post, a past one is rejected
+  let now = chrono::Utc::now();
+  let published_at = match body.post.published_at.is_empty() {
+    true => Some(now),
+    false => parse_date(&body.post.published_at).filter(|published_at| {
+      *published_at >= now && *published_at <= now + chrono::Duration::days(MAX_SCHEDULING_DAYS)
+    }),
+  };
+  if published_at.is_none() {
+    return StatusCode::BAD_REQUEST.into_response();
+  }
+  let published_at = published_at.unwrap();
+  let is_scheduled = published_at > now;
+
+  let expires_at = get_expires_at(
+    &body.post.expires_at,
+    &published_at,
+    &app_state.config_service.get_config(),
+  );
+  if expires_at.is_none() {
+    return StatusCode::BAD_REQUEST.into_response();
+  }
+  let expires_at = expires_at.unwrap();
+
   let compact_tags = app_state
     .tag_repository
     .get_many_compact_tags_by_ids(&body.post.tag_ids)
@@ -665,24 +1086,30 @@ pub async fn create_one_post(
   }
   let compact_tags = compact_tags.unwrap();
 
+  let db_post = DBPost {
+    poster_id: poster.id,
+    slug: slugify(&body.post.title),
+    is_published: true,
+    expires_at,
+    moderation_status: get_initial_moderation_status(
+      poster.is_trusted,
+      &similar_post_id,
+      &spam_score.verdict,
+    ),
+    moderation_reason: "".to_string(),
+    similar_post_id,
+    short_description: get_short_description(&body.post.description),
+    published_at: format_date(&published_at),
+    tag_ids: compact_tags.iter().map(|tag| tag.id).collect::<Vec<u32>>(),
+    ..body.post.clone()
+  };
   let post_id = app_state
     .post_repository
-    .create_one_post(&DBPost {
-      poster_id: poster.id,
-      slug: slugify(&body.post.title),
-      is_published: true,
-      // @TODO-ZM: summarize description using AI
-      short_description: body
-        .post
-        .description
-        .split_whitespace()
-        .take(20)
-        .collect::<Vec<&str>>()
-        .join(" "),
-      published_at: chrono::Utc::now().to_rfc3339(),
-      tag_ids: compact_tags.iter().map(|tag| tag.id).collect::<Vec<u32>>(),
-      ..body.post.clone()
-    })
+    .create_one_post(
+      &db_post,
+      &spam_score,
+      &db_post.to_db_post_revision(poster.id, PostRevisionReason::Created),
+    )
     .await;
 
   if !post_id.is_ok() {
@@ -698,20 +1125,39 @@ pub async fn create_one_post(
   }
   let post = post.unwrap();
 
-  let task_id = app_state
-    .task_repository
-    .create_one_task(DBTask {
-      name: TaskName::Indexing {
+  let task_names = match (&post.moderation_status, is_scheduled) {
+    // indexed once published, see PostCronJob
+    (PostModerationStatus::Approved, true) => vec![TaskName::Publishing {
+      model_name: "post".to_string(),
+      model_id: post.id,
+    }],
+    // the poster becomes searchable with their first published post
+    (PostModerationStatus::Approved, false) => vec![
+      TaskName::Indexing {
         model_name: "post".to_string(),
         model_id: post.id,
       },
-      status: TaskStatus::Pending,
-      r#type: TaskType::Automated,
-    })
-    .await;
-  if !task_id.is_ok() {
-    // @TODO-ZM: log error reason
-    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      TaskName::Indexing {
+        model_name: "account".to_string(),
+        model_id: post.poster_id,
+      },
+    ],
+    // indexed once approved, see the moderation controller
+    _ => vec![],
+  };
+  for name in task_names {
+    let task_id = app_state
+      .task_repository
+      .create_one_task(DBTask {
+        name,
+        status: TaskStatus::Pending,
+        r#type: TaskType::Automated,
+      })
+      .await;
+    if !task_id.is_ok() {
+      // @TODO-ZM: log error reason
+      return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
   }
 
   let poster = app_state
@@ -779,7 +1225,13 @@ pub async fn delete_one_post_by_id(
     }
   }
 
-  let delete_result = app_state.post_repository.delete_one_post_by_id(id).await;
+  let delete_result = app_state
+    .post_repository
+    .delete_one_post_by_id(
+      id,
+      &post.to_db_post_revision(scoped_token.id, PostRevisionReason::Deleted),
+    )
+    .await;
   if !delete_result.is_ok() {
     // @TODO-ZM: log error reason
     return StatusCode::INTERNAL_SERVER_ERROR.into_response();
@@ -805,11 +1257,385 @@ pub async fn delete_one_post_by_id(
   StatusCode::NO_CONTENT.into_response()
 }
 
+#[derive(Deserialize)]
+pub struct UpdateOnePostBody {
+  title: Option<String>,
+  description: Option<String>,
+  tag_ids: Option<Vec<u32>>,
+  // replaces all the attributes at once
+  attributes: Option<PostAttributes>,
+}
+
+pub async fn update_one_post_by_id(
+  ConnectInfo(ip): ConnectInfo<SocketAddr>,
+  State(app_state): State<AppState>,
+  scoped_token: ScopedToken,
+  Path(id): Path<u32>,
+  Json(body): Json<UpdateOnePostBody>,
+) -> impl IntoResponse {
+  let post = app_state.post_repository.get_one_post_by_id(id).await;
+  if !post.is_ok() {
+    match post {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let post = post.unwrap();
+
+  if post.poster_id != scoped_token.id {
+    return StatusCode::FORBIDDEN.into_response();
+  }
+
+  // @TODO-ZM: write a macro for this
+  match app_state.security_service.rate_limit(vec![
+    RateLimitConstraint {
+      id: format!("update_one_post_by_id-1-{}", scoped_token.id),
+      max_requests: 1,
+      duration_ms: 2000,
+    },
+    RateLimitConstraint {
+      id: format!("update_one_post_by_id-2-{}", ip.ip()),
+      max_requests: 60,
+      duration_ms: 60_000,
+    },
+  ]) {
+    Ok(_) => {}
+    Err(SecurityError::InternalError) => {
+      // @TODO-ZM: log error reason
+      return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    Err(SecurityError::RateLimitError) => {
+      return StatusCode::TOO_MANY_REQUESTS.into_response();
+    }
+  }
+
+  if body
+    .title
+    .as_ref()
+    .is_some_and(|title| title.trim().is_empty())
+    || body
+      .description
+      .as_ref()
+      .is_some_and(|description| description.trim().is_empty())
+    || body
+      .attributes
+      .as_ref()
+      .is_some_and(|attributes| !attributes.is_valid())
+  {
+    return StatusCode::BAD_REQUEST.into_response();
+  }
+
+  let tag_ids = match &body.tag_ids {
+    Some(tag_ids) => {
+      let mut tag_ids = tag_ids.clone();
+      sort_and_dedup_vec(&mut tag_ids);
+
+      let compact_tags = app_state
+        .tag_repository
+        .get_many_compact_tags_by_ids(&tag_ids)
+        .await;
+      if !compact_tags.is_ok() {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+      let compact_tags = compact_tags.unwrap();
+
+      // unlike on creation, unknown tags are rejected rather than dropped
+      if compact_tags.len() < tag_ids.len() {
+        return StatusCode::BAD_REQUEST.into_response();
+      }
+
+      Some(tag_ids)
+    }
+    None => None,
+  };
+
+  let poster = app_state
+    .account_repository
+    .get_one_account_by_id(post.poster_id)
+    .await;
+  if !poster.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let poster = poster.unwrap();
+
+  let spam_score = app_state.spam_service.get_post_spam_score(
+    body.title.as_ref().unwrap_or(&post.title),
+    body.description.as_ref().unwrap_or(&post.description),
+    &poster.email,
+  );
+  if spam_score.verdict == SpamVerdict::Reject {
+    return (
+      StatusCode::UNPROCESSABLE_ENTITY,
+      Json(json!({
+          "spam_rules": spam_score.matched_rules,
+      })),
+    )
+      .into_response();
+  }
+
+  let duplicate = app_state
+    .post_repository
+    .get_one_post_duplicate(
+      Some(post.id),
+      Some(post.poster_id),
+      body.title.as_ref().unwrap_or(&post.title),
+      body.description.as_ref().unwrap_or(&post.description),
+    )
+    .await;
+  if !duplicate.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let similar_post_id = match duplicate.unwrap() {
+    Some(PostDuplicate::Exact { post_id }) => {
+      return (
+        StatusCode::CONFLICT,
+        Json(json!({
+            "duplicate_post_id": post_id,
+        })),
+      )
+        .into_response();
+    }
+    Some(PostDuplicate::Near { post_id }) => Some(post_id),
+    None => None,
+  };
+
+  let was_live = post.is_live();
+  let updated_post = PartialPost {
+    id: None,
+    slug: body.title.as_ref().map(slugify),
+    title: body.title.clone(),
+    poster_id: None,
+    short_description: body.description.as_ref().map(get_short_description),
+    description: body.description.clone(),
+    description_html: body
+      .description
+      .as_ref()
+      .map(|description| render_markdown(description)),
+    tag_ids,
+    published_at: None,
+    is_published: None,
+    expires_at: None,
+    is_archived: None,
+    attributes: body.attributes.clone(),
+    moderation_status: get_edited_moderation_status(
+      poster.is_trusted,
+      &similar_post_id,
+      &spam_score.verdict,
+    ),
+    moderation_reason: None,
+    similar_post_id: Some(similar_post_id),
+  }
+  .to_post(post);
+
+  let update_result = app_state
+    .post_repository
+    .update_one_post(
+      &updated_post,
+      &spam_score,
+      &updated_post.to_db_post_revision(scoped_token.id, PostRevisionReason::Updated),
+    )
+    .await;
+  if !update_result.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+
+  // drafts are indexed once confirmed, scheduled posts once published, and archived posts once extended,
+  // and posts sent back to moderation are only unindexed
+  let task_names = match (was_live, updated_post.is_live()) {
+    (_, true) => vec![
+      TaskName::UndoIndexing {
+        model_name: "post".to_string(),
+        model_id: updated_post.id,
+      },
+      TaskName::Indexing {
+        model_name: "post".to_string(),
+        model_id: updated_post.id,
+      },
+    ],
+    (true, false) => vec![TaskName::UndoIndexing {
+      model_name: "post".to_string(),
+      model_id: updated_post.id,
+    }],
+    (false, false) => vec![],
+  };
+  for name in task_names {
+    let task_id = app_state
+      .task_repository
+      .create_one_task(DBTask {
+        name,
+        status: TaskStatus::Pending,
+        r#type: TaskType::Automated,
+      })
+      .await;
+    if !task_id.is_ok() {
+      // @TODO-ZM: log error reason
+      return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+  }
+
+  let compact_tags = app_state
+    .tag_repository
+    .get_many_compact_tags_by_ids(&updated_post.tag_ids)
+    .await;
+  if !compact_tags.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let compact_tags = compact_tags.unwrap();
+
+  Json(json!({
+      "post": updated_post,
+      "tags": compact_tags,
+  }))
+  .into_response()
+}
+
+// the one-click link of the expiry reminder email
+pub async fn extend_one_post_by_id(
+  ConnectInfo(ip): ConnectInfo<SocketAddr>,
+  State(app_state): State<AppState>,
+  Path(id): Path<u32>,
+  url_query: Query<ExtendPostQuery>,
+) -> impl IntoResponse {
+  match app_state
+    .security_service
+    .rate_limit(vec![RateLimitConstraint {
+      id: format!("extend_one_post_by_id-ip-{}", ip.ip()),
+      max_requests: 60,
+      duration_ms: 60_000,
+    }]) {
+    Ok(_) => {}
+    Err(SecurityError::InternalError) => {
+      // @TODO-ZM: log error reason
+      return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    Err(SecurityError::RateLimitError) => {
+      return StatusCode::TOO_MANY_REQUESTS.into_response();
+    }
+  }
+
+  let post = app_state.post_repository.get_one_post_by_id(id).await;
+  if !post.is_ok() {
+    match post {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let post = post.unwrap();
+
+  // only published posts get an expiry reminder, the code is kept until the post can be extended
+  if !post.is_published {
+    return StatusCode::BAD_REQUEST.into_response();
+  }
+
+  let kv_db_result = app_state.main_kv_db.compare_and_swap(
+    format!("post_extension_{}", id),
+    Some(url_query.code.as_bytes()),
+    None as Option<&[u8]>,
+  );
+  if !kv_db_result.is_ok() || kv_db_result.unwrap().is_err() {
+    // @TODO-ZM: log error reason
+    return StatusCode::UNAUTHORIZED.into_response();
+  }
+
+  let config = app_state.config_service.get_config();
+  let now = chrono::Utc::now();
+  // extends from the current expiry date when it's still ahead, capped by the maximum window
+  let extended_from = parse_date(&post.expires_at)
+    .filter(|expires_at| *expires_at > now)
+    .unwrap_or(now);
+  let expires_at = std::cmp::min(
+    extended_from + chrono::Duration::days(config.post_expiry_days),
+    now + chrono::Duration::days(config.post_max_expiry_days),
+  );
+  let expires_at = format_date(&expires_at);
+
+  let extended_post = Post {
+    expires_at,
+    is_archived: false,
+    ..post.clone()
+  };
+
+  let extend_result = app_state
+    .post_repository
+    .extend_one_post_by_id(
+      post.id,
+      &extended_post.expires_at,
+      &extended_post.to_db_post_revision(post.poster_id, PostRevisionReason::Extended),
+    )
+    .await;
+  if !extend_result.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+
+  // archived posts were un-indexed
+  if post.is_archived && extended_post.is_live() {
+    let task_id = app_state
+      .task_repository
+      .create_one_task(DBTask {
+        name: TaskName::Indexing {
+          model_name: "post".to_string(),
+          model_id: post.id,
+        },
+        status: TaskStatus::Pending,
+        r#type: TaskType::Automated,
+      })
+      .await;
+    if !task_id.is_ok() {
+      // @TODO-ZM: log error reason
+      return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+  }
+
+  let poster = app_state
+    .account_repository
+    .get_many_compact_accounts_by_ids(vec![post.poster_id])
+    .await;
+  if !poster.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let poster = poster.unwrap();
+  let poster = poster.first();
+  if poster.is_none() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let poster = poster.unwrap();
+
+  Redirect::to(&format!(
+    "{}{}",
+    config.web_base_url,
+    get_post_url(&extended_post.to_compact_post(), poster)
+  ))
+  .into_response()
+}
+
 pub fn create_post_router() -> Router<AppState> {
   Router::new()
     .route("/feed", axum::routing::get(get_all_posts_for_feed))
     .route("/:post_id", axum::routing::get(get_one_post_by_id))
     .route("/:post_id", axum::routing::delete(delete_one_post_by_id))
+    .route("/:post_id", axum::routing::patch(update_one_post_by_id))
+    .route(
+      "/:post_id/extend",
+      axum::routing::get(extend_one_post_by_id),
+    )
     .route(
       "/:post_id/similar",
       axum::routing::get(get_many_similar_posts_by_id),
@@ -0,0 +1,383 @@
+use crate::{
+  _entry::state::AppState,
+  _utils::{error::BootError, string::escape_html},
+  post::model::{PostModerationStatus, PostTrait},
+  post_revision::model::{PostRevisionReason, PostSnapshotTrait, SYSTEM_AUTHOR_ID},
+  task::model::{DBTask, TaskName, TaskStatus, TaskType},
+};
+use rand::{distributions::Alphanumeric, prelude::Distribution, thread_rng};
+use std::{
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+  },
+  time::Duration,
+};
+use tokio_cron_scheduler::Job;
+
+pub struct PostCronJob {
+  pub app_state: AppState,
+}
+
+async fn run_publishing_cron_job(app_state: AppState) {
+  tracing::info!("🚀 Publishing scheduled posts");
+
+  let tasks = app_state
+    .task_repository
+    .get_many_due_publishing_tasks(1000)
+    .await;
+  if tasks.is_err() {
+    tracing::error!("Error while getting publishing tasks");
+    return;
+  }
+  let tasks = tasks.unwrap();
+
+  if tasks.is_empty() {
+    tracing::info!("⏭️  No publishing tasks found, skipping");
+    return;
+  }
+
+  let post_ids = tasks
+    .iter()
+    .filter_map(|task| match &task.name {
+      TaskName::Publishing {
+        model_name,
+        model_id,
+      } if model_name == "post" => Some(*model_id),
+      _ => None,
+    })
+    .collect::<Vec<u32>>();
+
+  let posts = app_state
+    .post_repository
+    .get_many_posts_by_ids(post_ids)
+    .await;
+  if posts.is_err() {
+    tracing::error!("Error while getting scheduled posts");
+    return;
+  }
+  let posts = posts.unwrap();
+
+  let mut task_ids = vec![];
+  for task in tasks {
+    let post = match &task.name {
+      TaskName::Publishing { model_id, .. } => posts.iter().find(|post| post.id == *model_id),
+      _ => None,
+    };
+
+    // deleted, archived and unapproved posts have nothing left to publish
+    let post = match post {
+      Some(post)
+        if post.is_published
+          && post.moderation_status == PostModerationStatus::Approved
+          && !post.is_archived =>
+      {
+        post
+      }
+      _ => {
+        task_ids.push(task.id);
+        continue;
+      }
+    };
+
+    // still scheduled for later
+    if !post.is_live() {
+      continue;
+    }
+
+    tracing::info!("Publishing post {}", post.id);
+
+    // the poster becomes searchable with their first published post
+    let mut is_task_created = true;
+    for (model_name, model_id) in [("post", post.id), ("account", post.poster_id)] {
+      let task_id = app_state
+        .task_repository
+        .create_one_task(DBTask {
+          name: TaskName::Indexing {
+            model_name: model_name.to_string(),
+            model_id,
+          },
+          status: TaskStatus::Pending,
+          r#type: TaskType::Automated,
+        })
+        .await;
+      if task_id.is_err() {
+        tracing::error!("Error while creating indexing task for post {}", post.id);
+        is_task_created = false;
+      }
+    }
+
+    if !is_task_created {
+      continue;
+    }
+
+    // going live changes no column, the revision records when it happened
+    let post_revision_id = app_state
+      .post_revision_repository
+      .create_one_post_revision(
+        &post.to_db_post_revision(SYSTEM_AUTHOR_ID, PostRevisionReason::Published),
+      )
+      .await;
+    if post_revision_id.is_err() {
+      tracing::error!(
+        "Error while creating revision of published post {}",
+        post.id
+      );
+      continue;
+    }
+
+    task_ids.push(task.id);
+  }
+
+  if task_ids.is_empty() {
+    tracing::info!("⏭️  No scheduled posts are due, skipping");
+    return;
+  }
+
+  let completion_result = app_state
+    .task_repository
+    .complete_many_tasks_by_ids(task_ids)
+    .await;
+  if completion_result.is_err() {
+    tracing::error!("Error while completing publishing tasks");
+    return;
+  }
+
+  tracing::info!("✅ Publishing scheduled posts done");
+}
+
+async fn run_archiving_cron_job(app_state: AppState) {
+  tracing::info!("🚀 Archiving expired posts");
+
+  let posts = app_state.post_repository.get_many_expired_posts(100).await;
+  if posts.is_err() {
+    tracing::error!("Error while getting expired posts");
+    return;
+  }
+  let posts = posts.unwrap();
+
+  if posts.is_empty() {
+    tracing::info!("⏭️  No expired posts found, skipping");
+    return;
+  }
+
+  tracing::info!("Archiving {} expired posts", posts.len());
+
+  let archiving_result = app_state
+    .post_repository
+    .archive_many_posts(
+      &posts
+        .iter()
+        .map(|post| post.to_db_post_revision(SYSTEM_AUTHOR_ID, PostRevisionReason::Archived))
+        .collect(),
+    )
+    .await;
+  if archiving_result.is_err() {
+    tracing::error!("Error while archiving expired posts");
+    return;
+  }
+
+  for post in posts {
+    let task_id = app_state
+      .task_repository
+      .create_one_task(DBTask {
+        name: TaskName::UndoIndexing {
+          model_name: "post".to_string(),
+          model_id: post.id,
+        },
+        status: TaskStatus::Pending,
+        r#type: TaskType::Automated,
+      })
+      .await;
+    if task_id.is_err() {
+      tracing::error!("Error while creating un-indexing task for post {}", post.id);
+    }
+  }
+
+  tracing::info!("✅ Archiving expired posts done");
+}
+
+async fn run_expiry_reminder_cron_job(app_state: AppState) {
+  tracing::info!("🚀 Reminding posters of expiring posts");
+
+  let config = app_state.config_service.get_config();
+
+  let posts = app_state
+    .post_repository
+    .get_many_expiring_posts(config.post_expiry_reminder_days, 20)
+    .await;
+  if posts.is_err() {
+    tracing::error!("Error while getting expiring posts");
+    return;
+  }
+  let posts = posts.unwrap();
+
+  if posts.is_empty() {
+    tracing::info!("⏭️  No expiring posts found, skipping");
+    return;
+  }
+
+  tracing::info!("Reminding posters of {} expiring posts", posts.len());
+
+  for post in posts {
+    let poster = app_state
+      .account_repository
+      .get_one_account_by_id(post.poster_id)
+      .await;
+    if poster.is_err() {
+      tracing::error!("Error while getting poster of post {}", post.id);
+      continue;
+    }
+    let poster = poster.unwrap();
+
+    let extension_code: String = Alphanumeric
+      .sample_iter(&mut thread_rng())
+      .take(32)
+      .map(char::from)
+      .collect();
+
+    let kv_db_result = app_state.main_kv_db.insert(
+      format!("post_extension_{}", post.id),
+      extension_code.as_bytes(),
+    );
+    if kv_db_result.is_err() {
+      tracing::error!("Error while storing extension code of post {}", post.id);
+      continue;
+    }
+
+    let email_result = app_state
+      .email_service
+      .send_one_email(
+        &poster.email,
+        &"Your job post is about to close".to_string(),
+        &format!(
+          r#"Your job post at dzjob.io with title:
+
+{}
+
+will close on {} (UTC), and will no longer show in the feed nor in search results.
+
+If the position is still open, you can keep it up for {} more days by clicking the link below:
+
+<a href="{}/posts/{}/extend?code={}">Keep my job post open</a>
+
+Thank you for using our service!
+
+DZ Job Team
+contact@dzjob.io
+https://www.dzjob.io
+"#,
+          escape_html(&post.title),
+          post.expires_at.get(..10).unwrap_or(&post.expires_at),
+          config.post_expiry_days,
+          config.api_base_url,
+          post.id,
+          extension_code,
+        ),
+      )
+      .await;
+    if email_result.is_err() {
+      tracing::error!("Error while sending expiry reminder of post {}", post.id);
+      continue;
+    }
+
+    let remind_result = app_state
+      .post_repository
+      .remind_one_post_of_expiry_by_id(post.id)
+      .await;
+    if remind_result.is_err() {
+      tracing::error!("Error while marking post {} as reminded", post.id);
+    }
+  }
+
+  tracing::info!("✅ Reminding posters of expiring posts done");
+}
+
+impl PostCronJob {
+  pub fn create_publishing_cron_job(&self) -> Result<Job, BootError> {
+    let app_state = self.app_state.clone();
+    let is_job_running = Arc::new(AtomicBool::new(false));
+
+    let job = Job::new_repeated_async(Duration::from_secs(30), move |_, __| {
+      let app_state = app_state.clone();
+      let is_job_running = is_job_running.clone();
+
+      return Box::pin(async move {
+        let compare_and_swap_result =
+          is_job_running.compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed);
+        if compare_and_swap_result.is_ok() && compare_and_swap_result.unwrap() == false {
+          run_publishing_cron_job(app_state.clone()).await;
+          is_job_running.store(false, Ordering::Relaxed);
+        } else {
+          tracing::info!("⏳ Still publishing scheduled posts... ");
+        }
+      });
+    });
+
+    if job.is_err() {
+      tracing::error!("Error while creating publishing cron job");
+      return Err(BootError::CronJobSetupError);
+    }
+    let job = job.unwrap();
+
+    Ok(job)
+  }
+
+  pub fn create_archiving_cron_job(&self) -> Result<Job, BootError> {
+    let app_state = self.app_state.clone();
+    let is_job_running = Arc::new(AtomicBool::new(false));
+
+    let job = Job::new_repeated_async(Duration::from_secs(60), move |_, __| {
+      let app_state = app_state.clone();
+      let is_job_running = is_job_running.clone();
+
+      return Box::pin(async move {
+        let compare_and_swap_result =
+          is_job_running.compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed);
+        if compare_and_swap_result.is_ok() && compare_and_swap_result.unwrap() == false {
+          run_archiving_cron_job(app_state.clone()).await;
+          is_job_running.store(false, Ordering::Relaxed);
+        } else {
+          tracing::info!("⏳ Still archiving expired posts... ");
+        }
+      });
+    });
+
+    if job.is_err() {
+      tracing::error!("Error while creating archiving cron job");
+      return Err(BootError::CronJobSetupError);
+    }
+    let job = job.unwrap();
+
+    Ok(job)
+  }
+
+  pub fn create_expiry_reminder_cron_job(&self) -> Result<Job, BootError> {
+    let app_state = self.app_state.clone();
+    let is_job_running = Arc::new(AtomicBool::new(false));
+
+    let job = Job::new_repeated_async(Duration::from_secs(60), move |_, __| {
+      let app_state = app_state.clone();
+      let is_job_running = is_job_running.clone();
+
+      return Box::pin(async move {
+        let compare_and_swap_result =
+          is_job_running.compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed);
+        if compare_and_swap_result.is_ok() && compare_and_swap_result.unwrap() == false {
+          run_expiry_reminder_cron_job(app_state.clone()).await;
+          is_job_running.store(false, Ordering::Relaxed);
+        } else {
+          tracing::info!("⏳ Still reminding posters of expiring posts... ");
+        }
+      });
+    });
+
+    if job.is_err() {
+      tracing::error!("Error while creating expiry reminder cron job");
+      return Err(BootError::CronJobSetupError);
+    }
+    let job = job.unwrap();
+
+    Ok(job)
+  }
+}
@@ -1,3 +1,4 @@
 pub mod controller;
+pub mod cron_job;
 pub mod model;
 pub mod repository;
@@ -1,8 +1,175 @@
 use serde::{Deserialize, Serialize};
+use strum_macros::Display;
 use utility_types::{omit, partial, pick};
 
+use crate::_utils::{database::DBFacetCount, wilaya::is_wilaya_code};
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ContractType {
+  FullTime,
+  PartTime,
+  FixedTerm,
+  Freelance,
+  Internship,
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum RemotePolicy {
+  OnSite,
+  Hybrid,
+  Remote,
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum SalaryCurrency {
+  #[serde(rename = "DZD")]
+  Dzd,
+  #[serde(rename = "EUR")]
+  Eur,
+  #[serde(rename = "USD")]
+  Usd,
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SalaryPeriod {
+  Hourly,
+  Daily,
+  Monthly,
+  Yearly,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PostSalary {
+  pub min: u32,
+  pub max: u32,
+  pub currency: SalaryCurrency,
+  pub period: SalaryPeriod,
+}
+
+// only approved posts go live, posts from trusted accounts are approved right away
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PostModerationStatus {
+  #[default]
+  Pending,
+  Approved,
+  Rejected,
+}
+
+// compared on the title and the description, against recent posts
+#[derive(Debug, Clone, PartialEq)]
+pub enum PostDuplicate {
+  // same words in the same order
+  Exact { post_id: u32 },
+  // a few words apart, see get_minhash
+  Near { post_id: u32 },
+}
+
+// all optional, posts created before they existed have none of them
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PostAttributes {
+  pub wilaya_code: Option<u32>,
+  pub contract_type: Option<ContractType>,
+  pub remote_policy: Option<RemotePolicy>,
+  pub salary: Option<PostSalary>,
+}
+
+pub trait PostAttributesTrait {
+  fn is_valid(&self) -> bool;
+}
+
+impl PostAttributesTrait for PostAttributes {
+  fn is_valid(&self) -> bool {
+    if let Some(wilaya_code) = self.wilaya_code {
+      if !is_wilaya_code(wilaya_code) {
+        return false;
+      }
+    }
+
+    if let Some(salary) = &self.salary {
+      if salary.max == 0 || salary.min > salary.max {
+        return false;
+      }
+    }
+
+    true
+  }
+}
+
+#[derive(Debug, Default)]
+pub struct PostAttributeFilters {
+  pub wilaya_code: Option<u32>,
+  pub contract_type: Option<ContractType>,
+  pub remote_policy: Option<RemotePolicy>,
+  pub salary_currency: Option<SalaryCurrency>,
+  pub salary_period: Option<SalaryPeriod>,
+  // matches posts whose salary range reaches it, only set along a currency and a period
+  pub min_salary: Option<u32>,
+}
+
+// what the feed can be narrowed down to, a post must have all the tags
+#[derive(Debug, Default)]
+pub struct PostFeedFilters {
+  pub tag_ids: Vec<u32>,
+  pub poster_id: Option<u32>,
+  pub attributes: PostAttributeFilters,
+}
+
+// the feed is sorted by published_at then id, so the last post of a page is enough to resume from
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostFeedCursor {
+  pub published_at: String,
+  pub id: u32,
+}
+
+// cursors are hex encoded so clients treat them as opaque
+pub fn parse_post_feed_cursor(cursor: &str) -> Option<PostFeedCursor> {
+  if !cursor.len().is_multiple_of(2) {
+    return None;
+  }
+
+  let bytes = (0..cursor.len())
+    .step_by(2)
+    .map(|index| u8::from_str_radix(cursor.get(index..index + 2)?, 16).ok())
+    .collect::<Option<Vec<u8>>>()?;
+  let cursor = String::from_utf8(bytes).ok()?;
+
+  let (published_at, id) = cursor.rsplit_once('_')?;
+  let id = id.parse::<u32>().ok()?;
+
+  Some(PostFeedCursor {
+    published_at: published_at.to_string(),
+    id,
+  })
+}
+
+// tags and wilayas are counted by id, the others by their serialized value
+#[derive(Debug, Serialize, Default)]
+pub struct PostFacets {
+  pub tags: Vec<DBFacetCount>,
+  pub poster_types: Vec<DBFacetCount>,
+  pub wilayas: Vec<DBFacetCount>,
+  pub contract_types: Vec<DBFacetCount>,
+}
+
+pub fn get_facet_tag_ids(facets: &PostFacets) -> Vec<u32> {
+  facets
+    .tags
+    .iter()
+    .filter_map(|facet_count| facet_count.value.parse::<u32>().ok())
+    .collect()
+}
+
 #[omit(DBPost, [id], [Debug, Serialize, Deserialize, Clone])]
-#[pick(CompactPost, [id, slug, title, poster_id, short_description, tag_ids, published_at], [Debug, Serialize, Deserialize, Clone])]
+#[pick(CompactPost, [id, slug, title, poster_id, short_description, tag_ids, published_at, attributes], [Debug, Serialize, Deserialize, Clone])]
 #[partial(PartialPost)]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Post {
@@ -11,14 +178,46 @@ pub struct Post {
   pub title: String,
   pub poster_id: u32,
   pub short_description: String,
+  // markdown, see render_markdown
   pub description: String,
+  // rendered from the description when read, it's never stored
+  #[serde(default)]
+  pub description_html: String,
   pub tag_ids: Vec<u32>,
   pub published_at: String,
   pub is_published: bool,
+  #[serde(default)]
+  pub expires_at: String,
+  #[serde(default)]
+  pub is_archived: bool,
+  #[serde(default)]
+  pub attributes: PostAttributes,
+  #[serde(default)]
+  pub moderation_status: PostModerationStatus,
+  // why the post was rejected, empty otherwise
+  #[serde(default)]
+  pub moderation_reason: String,
+  // a near duplicate is sent to moderation, pointing at the post it looks like
+  #[serde(default)]
+  pub similar_post_id: Option<u32>,
+}
+
+pub trait CompactPostTrait {
+  fn to_feed_cursor(&self) -> String;
+}
+
+impl CompactPostTrait for CompactPost {
+  fn to_feed_cursor(&self) -> String {
+    format!("{}_{}", self.published_at, self.id)
+      .bytes()
+      .map(|byte| format!("{:02x}", byte))
+      .collect()
+  }
 }
 
 pub trait PostTrait {
   fn to_compact_post(&self) -> CompactPost;
+  fn is_live(&self) -> bool;
 }
 
 impl PostTrait for Post {
@@ -31,8 +230,23 @@ impl PostTrait for Post {
       short_description: self.short_description.clone(),
       tag_ids: self.tag_ids.clone(),
       published_at: self.published_at.clone(),
+      attributes: self.attributes.clone(),
     }
   }
+
+  // published and approved, and not scheduled for later nor archived
+  fn is_live(&self) -> bool {
+    // stored dates don't share the same sub-second format, so only their first 19 characters are compared
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+
+    self.is_published
+      && self.moderation_status == PostModerationStatus::Approved
+      && !self.is_archived
+      && self
+        .published_at
+        .get(..19)
+        .is_some_and(|published_at| published_at <= now.as_str())
+  }
 }
 
 pub trait PartialPostTrait {
@@ -54,12 +268,30 @@ impl PartialPostTrait for PartialPost {
         .description
         .clone()
         .unwrap_or(fallback_post.description),
+      description_html: self
+        .description_html
+        .clone()
+        .unwrap_or(fallback_post.description_html),
       tag_ids: self.tag_ids.clone().unwrap_or(fallback_post.tag_ids),
       is_published: self.is_published.unwrap_or(fallback_post.is_published),
       published_at: self
         .published_at
         .clone()
         .unwrap_or(fallback_post.published_at),
+      expires_at: self.expires_at.clone().unwrap_or(fallback_post.expires_at),
+      is_archived: self.is_archived.unwrap_or(fallback_post.is_archived),
+      attributes: self.attributes.clone().unwrap_or(fallback_post.attributes),
+      moderation_status: self
+        .moderation_status
+        .clone()
+        .unwrap_or(fallback_post.moderation_status),
+      moderation_reason: self
+        .moderation_reason
+        .clone()
+        .unwrap_or(fallback_post.moderation_reason),
+      similar_post_id: self
+        .similar_post_id
+        .unwrap_or(fallback_post.similar_post_id),
     }
   }
 }
@@ -1,9 +1,280 @@
 use serde_json::json;
-use sqlx::{Pool, Row, Sqlite};
+use sqlx::{sqlite::SqliteRow, Pool, QueryBuilder, Row, Sqlite};
 use std::sync::Arc;
 
-use super::model::{CompactPost, DBPost, Post};
-use crate::_utils::{database::DBOrderDirection, error::DataAccessError};
+use super::model::{
+  CompactPost, DBPost, Post, PostAttributeFilters, PostAttributes, PostDuplicate, PostFacets,
+  PostFeedCursor, PostFeedFilters, PostModerationStatus,
+};
+use crate::{
+  _utils::{
+    database::{DBFacetCount, DBOrderDirection},
+    error::DataAccessError,
+    fingerprint::{get_content_hash, get_content_tokens, get_minhash, get_minhash_similarity},
+    markdown::render_markdown,
+    query::DBPaginationQuery,
+  },
+  post_revision::{model::DBPostRevision, repository::insert_one_post_revision},
+  spam::model::{PostSpamScore, SpamRuleMatch, SpamScore},
+};
+
+// only the most frequent tags are counted, the other facets have a bounded number of values
+const MAX_TAG_FACET_COUNT: u32 = 30;
+// duplicates are looked for among the posts created this recently, and among the poster's own older posts,
+// the most recent first, up to a number of candidates as each one is compared in memory
+const DUPLICATE_CORPUS_DAYS: u32 = 30;
+const DUPLICATE_POSTER_CORPUS_DAYS: u32 = 365;
+const MAX_DUPLICATE_CANDIDATE_COUNT: u32 = 5000;
+// reworded reposts score above 0.9, a company's template reused for another job around 0.75
+const NEAR_DUPLICATE_MIN_SIMILARITY: f64 = 0.85;
+// shorter contents are too likely to look alike by chance
+const NEAR_DUPLICATE_MIN_TOKEN_COUNT: usize = 10;
+
+// the salary is only set when all of its columns are,
+// the attribute columns are shared by the post table and the post_revision table
+pub fn get_post_attributes_from_row(row: &SqliteRow) -> serde_json::Value {
+  let salary = match (
+    row.get::<Option<u32>, _>("salary_min"),
+    row.get::<Option<u32>, _>("salary_max"),
+    row.get::<Option<String>, _>("salary_currency"),
+    row.get::<Option<String>, _>("salary_period"),
+  ) {
+    (Some(min), Some(max), Some(currency), Some(period)) => json!({
+      "min": min,
+      "max": max,
+      "currency": currency,
+      "period": period,
+    }),
+    _ => serde_json::Value::Null,
+  };
+
+  json!({
+    "wilaya_code": row.get::<Option<u32>, _>("wilaya_code"),
+    "contract_type": row.get::<Option<String>, _>("contract_type"),
+    "remote_policy": row.get::<Option<String>, _>("remote_policy"),
+    "salary": salary,
+  })
+}
+
+// the attribute columns are shared by the post table and the search's indexed_post table
+pub fn push_post_attribute_filters(
+  query_builder: &mut QueryBuilder<'_, Sqlite>,
+  table: &str,
+  filters: &PostAttributeFilters,
+) {
+  if let Some(wilaya_code) = filters.wilaya_code {
+    query_builder.push(format!(" AND {}.wilaya_code = ", table));
+    query_builder.push_bind(wilaya_code);
+  }
+
+  if let Some(contract_type) = &filters.contract_type {
+    query_builder.push(format!(" AND {}.contract_type = ", table));
+    query_builder.push_bind(contract_type.to_string());
+  }
+
+  if let Some(remote_policy) = &filters.remote_policy {
+    query_builder.push(format!(" AND {}.remote_policy = ", table));
+    query_builder.push_bind(remote_policy.to_string());
+  }
+
+  if let Some(salary_currency) = &filters.salary_currency {
+    query_builder.push(format!(" AND {}.salary_currency = ", table));
+    query_builder.push_bind(salary_currency.to_string());
+  }
+
+  if let Some(salary_period) = &filters.salary_period {
+    query_builder.push(format!(" AND {}.salary_period = ", table));
+    query_builder.push_bind(salary_period.to_string());
+  }
+
+  if let Some(min_salary) = filters.min_salary {
+    query_builder.push(format!(" AND {}.salary_max >= ", table));
+    query_builder.push_bind(min_salary);
+  }
+}
+
+// pushes the facet counts of the posts selected by the "matched_post" CTE, which must have
+// the "id", "tag_ids", "poster_type", "wilaya_code" and "contract_type" columns
+pub fn push_post_facets_query(query_builder: &mut QueryBuilder<'_, Sqlite>) {
+  query_builder.push(
+    r#"
+    SELECT * FROM (
+      SELECT 'tags' AS facet, CAST(matched_post_tag.value AS TEXT) AS value, COUNT(*) AS count
+      FROM matched_post, json_each('[' || matched_post.tag_ids || ']') AS matched_post_tag
+      GROUP BY value
+      ORDER BY count DESC, value ASC
+      LIMIT "#,
+  );
+  query_builder.push_bind(MAX_TAG_FACET_COUNT);
+  query_builder.push(
+    r#"
+    )
+    UNION ALL
+    SELECT 'poster_types' AS facet, poster_type AS value, COUNT(*) AS count
+    FROM matched_post
+    GROUP BY value
+    UNION ALL
+    SELECT 'wilayas' AS facet, CAST(wilaya_code AS TEXT) AS value, COUNT(*) AS count
+    FROM matched_post
+    WHERE wilaya_code IS NOT NULL
+    GROUP BY value
+    UNION ALL
+    SELECT 'contract_types' AS facet, contract_type AS value, COUNT(*) AS count
+    FROM matched_post
+    WHERE contract_type IS NOT NULL
+    GROUP BY value
+    ORDER BY facet, count DESC, value ASC
+    "#,
+  );
+}
+
+pub fn get_post_facets_from_rows(rows: &Vec<SqliteRow>) -> PostFacets {
+  let mut post_facets = PostFacets::default();
+
+  for row in rows {
+    let facet_count = DBFacetCount {
+      value: row.get::<String, _>("value"),
+      count: row.get::<i64, _>("count") as u32,
+    };
+    match row.get::<String, _>("facet").as_str() {
+      "tags" => post_facets.tags.push(facet_count),
+      "poster_types" => post_facets.poster_types.push(facet_count),
+      "wilayas" => post_facets.wilayas.push(facet_count),
+      "contract_types" => post_facets.contract_types.push(facet_count),
+      _ => {}
+    }
+  }
+
+  post_facets
+}
+
+// the salary columns are all set or all NULL
+pub fn get_salary_columns(
+  attributes: &PostAttributes,
+) -> (Option<u32>, Option<u32>, Option<String>, Option<String>) {
+  match &attributes.salary {
+    Some(salary) => (
+      Some(salary.min),
+      Some(salary.max),
+      Some(salary.currency.to_string()),
+      Some(salary.period.to_string()),
+    ),
+    None => (None, None, None, None),
+  }
+}
+
+fn get_post_from_row(row: &SqliteRow) -> Result<Post, DataAccessError> {
+  let tag_ids = row.get::<String, _>("tag_ids");
+  let tag_ids = tag_ids
+    .split(",")
+    .filter(|id| !id.is_empty())
+    .map(|id| id.parse::<u32>())
+    .collect::<Vec<Result<u32, _>>>();
+  if tag_ids.iter().any(|id| id.is_err()) {
+    tracing::error!(
+      "Error while getting post, on parsing tag_ids, error: {:?}",
+      tag_ids
+    );
+    return Err(DataAccessError::InternalError);
+  }
+
+  let tag_ids = tag_ids
+    .iter()
+    .map(|id| id.clone().unwrap())
+    .collect::<Vec<u32>>();
+
+  let json_post = json!({
+    "id": row.get::<u32, _>("id"),
+    "slug": row.get::<String, _>("slug"),
+    "title": row.get::<String, _>("title"),
+    "poster_id": row.get::<u32, _>("poster_id"),
+    "short_description": row.get::<String, _>("short_description"),
+    "description": row.get::<String, _>("description"),
+    "description_html": render_markdown(row.get::<&str, _>("description")),
+    "tag_ids": tag_ids,
+    "published_at": row.get::<String, _>("published_at"),
+    "attributes": get_post_attributes_from_row(row),
+    "is_published": row.get::<bool, _>("is_published"),
+    "expires_at": row.get::<String, _>("expires_at"),
+    "is_archived": row.get::<bool, _>("is_archived"),
+    "moderation_status": row.get::<String, _>("moderation_status"),
+    "moderation_reason": row.get::<String, _>("moderation_reason"),
+    "similar_post_id": row.get::<Option<u32>, _>("similar_post_id"),
+  });
+
+  let post = serde_json::from_value::<Post>(json_post);
+  if post.is_err() {
+    tracing::error!("Error while deserializing post: {:?}", post.err());
+    return Err(DataAccessError::InternalError);
+  }
+
+  Ok(post.unwrap())
+}
+
+fn get_compact_post_from_row(row: &SqliteRow) -> Result<CompactPost, DataAccessError> {
+  let tag_ids = row.get::<String, _>("tag_ids");
+  let tag_ids = tag_ids
+    .split(",")
+    .filter(|id| !id.is_empty())
+    .map(|id| id.parse::<u32>())
+    .collect::<Vec<Result<u32, _>>>();
+  if tag_ids.iter().any(|id| id.is_err()) {
+    tracing::error!(
+      "Error while getting compact post, on parsing tag_ids, error: {:?}",
+      tag_ids
+    );
+    return Err(DataAccessError::InternalError);
+  }
+
+  let tag_ids = tag_ids
+    .iter()
+    .map(|id| id.clone().unwrap())
+    .collect::<Vec<u32>>();
+
+  let json_compact_post = json!({
+    "id": row.get::<u32, _>("id"),
+    "slug": row.get::<String, _>("slug"),
+    "title": row.get::<String, _>("title"),
+    "poster_id": row.get::<u32, _>("poster_id"),
+    "short_description": row.get::<String, _>("short_description"),
+    "tag_ids": tag_ids,
+    "published_at": row.get::<String, _>("published_at"),
+    "attributes": get_post_attributes_from_row(row),
+  });
+
+  let compact_post = serde_json::from_value::<CompactPost>(json_compact_post);
+  if compact_post.is_err() {
+    tracing::error!(
+      "Error while deserializing compact post: {:?}",
+      compact_post.err()
+    );
+    return Err(DataAccessError::InternalError);
+  }
+
+  Ok(compact_post.unwrap())
+}
+
+// narrows down the published posts of the "post" table
+fn push_post_feed_filters(query_builder: &mut QueryBuilder<'_, Sqlite>, filters: &PostFeedFilters) {
+  if let Some(poster_id) = filters.poster_id {
+    query_builder.push(" AND post.poster_id = ");
+    query_builder.push_bind(poster_id);
+  }
+
+  if !filters.tag_ids.is_empty() {
+    query_builder.push(
+      " AND (SELECT COUNT(DISTINCT value) FROM json_each('[' || post.tag_ids || ']') WHERE value IN (",
+    );
+    let mut separated = query_builder.separated(", ");
+    for tag_id in &filters.tag_ids {
+      separated.push_bind(*tag_id);
+    }
+    separated.push_unseparated(")) = ");
+    query_builder.push_bind(filters.tag_ids.len() as i64);
+  }
+
+  push_post_attribute_filters(query_builder, "post", &filters.attributes);
+}
 
 pub struct PostRepository {
   main_sql_db: Arc<Pool<Sqlite>>,
@@ -28,25 +299,20 @@ impl PostRepository {
     }
     let mut conn = conn.unwrap();
 
-    // @TODO-ZM: figure out how query $ replacement work, there is some unneeded "magic" here
-    let db_result = sqlx::query(
-      format!(
-        r#"
-      SELECT id, slug, title, poster_id, short_description, tag_ids, published_at
+    let mut query_builder = QueryBuilder::<Sqlite>::new(
+      r#"
+      SELECT id, slug, title, poster_id, short_description, tag_ids, published_at, wilaya_code, contract_type, remote_policy, salary_min, salary_max, salary_currency, salary_period
       FROM post
-      WHERE is_published = 1 AND is_deleted = 0
-      ORDER BY {} {}
-      LIMIT $1
-      OFFSET $2
-      "#,
-        order_by, order_direction,
-      )
-      .as_str(),
-    )
-    .bind(limit)
-    .bind(start)
-    .fetch_all(&mut *conn)
-    .await;
+      WHERE is_published = 1 AND is_deleted = 0 AND is_archived = 0
+        AND substr(published_at, 1, 19) <= strftime('%Y-%m-%dT%H:%M:%S', 'now')
+        AND moderation_status = 'approved'"#,
+    );
+    query_builder.push(format!(" ORDER BY {} {} LIMIT ", order_by, order_direction));
+    query_builder.push_bind(limit);
+    query_builder.push(" OFFSET ");
+    query_builder.push_bind(start);
+
+    let db_result = query_builder.build().fetch_all(&mut *conn).await;
 
     if db_result.is_err() {
       tracing::error!(
@@ -86,6 +352,7 @@ impl PostRepository {
         "short_description": row.get::<String, _>("short_description"),
         "tag_ids": tag_ids,
         "published_at": row.get::<String, _>("published_at"),
+        "attributes": get_post_attributes_from_row(&row),
       });
       let compact_compact_post = serde_json::from_value::<CompactPost>(json_compact_post);
       if compact_compact_post.is_err() {
@@ -102,6 +369,66 @@ impl PostRepository {
     Ok(compact_posts)
   }
 
+  // keyset paginated, so pages stay stable while new posts are published,
+  // a cursor takes precedence over the start
+  pub async fn get_many_compact_posts_for_feed(
+    &self,
+    filters: &PostFeedFilters,
+    cursor: &Option<PostFeedCursor>,
+    limit: u32,
+    start: u32,
+  ) -> Result<Vec<CompactPost>, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let mut query_builder = QueryBuilder::<Sqlite>::new(
+      r#"
+      SELECT id, slug, title, poster_id, short_description, tag_ids, published_at, wilaya_code, contract_type, remote_policy, salary_min, salary_max, salary_currency, salary_period
+      FROM post
+      WHERE is_published = 1 AND is_deleted = 0 AND is_archived = 0
+        AND substr(published_at, 1, 19) <= strftime('%Y-%m-%dT%H:%M:%S', 'now')
+        AND moderation_status = 'approved'"#,
+    );
+    push_post_feed_filters(&mut query_builder, filters);
+
+    if let Some(cursor) = cursor {
+      query_builder.push(" AND (published_at < ");
+      query_builder.push_bind(cursor.published_at.clone());
+      query_builder.push(" OR (published_at = ");
+      query_builder.push_bind(cursor.published_at.clone());
+      query_builder.push(" AND id < ");
+      query_builder.push_bind(cursor.id);
+      query_builder.push("))");
+    }
+
+    query_builder.push(" ORDER BY published_at DESC, id DESC LIMIT ");
+    query_builder.push_bind(limit);
+    query_builder.push(" OFFSET ");
+    query_builder.push_bind(match cursor {
+      Some(_) => 0,
+      None => start,
+    });
+
+    let db_result = query_builder.build().fetch_all(&mut *conn).await;
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting many compact posts for feed: {:?}",
+        db_result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+
+    db_result
+      .unwrap()
+      .iter()
+      .map(get_compact_post_from_row)
+      .collect::<Result<Vec<CompactPost>, DataAccessError>>()
+  }
+
   pub async fn get_many_compact_posts_by_ids(
     &self,
     ids: Vec<u32>,
@@ -116,7 +443,7 @@ impl PostRepository {
     let db_result = sqlx::query(
       format!(
         r#"
-      SELECT id, slug, title, poster_id, short_description, tag_ids, published_at
+      SELECT id, slug, title, poster_id, short_description, tag_ids, published_at, wilaya_code, contract_type, remote_policy, salary_min, salary_max, salary_currency, salary_period
       FROM post
       WHERE id IN ({}) AND is_deleted = 0
       "#,
@@ -171,6 +498,7 @@ impl PostRepository {
         "short_description": row.get::<String, _>("short_description"),
         "tag_ids": tag_ids,
         "published_at": row.get::<String, _>("published_at"),
+        "attributes": get_post_attributes_from_row(&row),
       });
 
       let compact_compact_post = serde_json::from_value::<CompactPost>(json_compact_post);
@@ -189,6 +517,103 @@ impl PostRepository {
     Ok(compact_posts)
   }
 
+  // the latest published posts of each poster, at most `limit_per_poster` each
+  pub async fn get_many_published_compact_posts_by_poster_ids(
+    &self,
+    poster_ids: &Vec<u32>,
+    limit_per_poster: u32,
+  ) -> Result<Vec<CompactPost>, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      format!(
+        r#"
+      SELECT id, slug, title, poster_id, short_description, tag_ids, published_at, wilaya_code, contract_type, remote_policy, salary_min, salary_max, salary_currency, salary_period
+      FROM (
+        SELECT *, ROW_NUMBER() OVER (PARTITION BY poster_id ORDER BY published_at DESC) AS poster_row_number
+        FROM post
+        WHERE poster_id IN ({}) AND is_published = 1 AND is_deleted = 0 AND is_archived = 0
+          AND substr(published_at, 1, 19) <= strftime('%Y-%m-%dT%H:%M:%S', 'now')
+          AND moderation_status = 'approved'
+      )
+      WHERE poster_row_number <= $1
+      ORDER BY published_at DESC
+      "#,
+        poster_ids
+          .iter()
+          .map(|id| id.to_string())
+          .collect::<Vec<String>>()
+          .join(",")
+      )
+      .as_str(),
+    )
+    .bind(limit_per_poster)
+    .fetch_all(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting many published compact posts by poster ids: {:?}",
+        db_result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+    let db_result = db_result.unwrap();
+
+    let mut compact_posts = vec![];
+
+    for row in db_result {
+      let tag_ids = row.get::<String, _>("tag_ids");
+      let tag_ids = tag_ids
+        .split(",")
+        .filter(|id| !id.is_empty())
+        .map(|id| id.parse::<u32>())
+        .collect::<Vec<Result<u32, _>>>();
+      if tag_ids.iter().any(|id| id.is_err()) {
+        tracing::error!(
+          "Error while getting many published compact posts by poster ids, on parsing tag_ids, error: {:?}",
+          tag_ids
+        );
+        return Err(DataAccessError::InternalError);
+      }
+
+      let tag_ids = tag_ids
+        .iter()
+        .map(|id| id.clone().unwrap())
+        .collect::<Vec<u32>>();
+
+      let json_compact_post = json!({
+        "id": row.get::<u32, _>("id"),
+        "slug": row.get::<String, _>("slug"),
+        "title": row.get::<String, _>("title"),
+        "poster_id": row.get::<u32, _>("poster_id"),
+        "short_description": row.get::<String, _>("short_description"),
+        "tag_ids": tag_ids,
+        "published_at": row.get::<String, _>("published_at"),
+        "attributes": get_post_attributes_from_row(&row),
+      });
+
+      let compact_post = serde_json::from_value::<CompactPost>(json_compact_post);
+      if compact_post.is_err() {
+        tracing::error!(
+          "Error while getting many published compact posts by poster ids, on parsing compact_post, error: {:?}",
+          compact_post.err()
+        );
+        return Err(DataAccessError::InternalError);
+      }
+      let compact_post = compact_post.unwrap();
+
+      compact_posts.push(compact_post);
+    }
+
+    Ok(compact_posts)
+  }
+
   pub async fn get_many_posts_by_ids(&self, ids: Vec<u32>) -> Result<Vec<Post>, DataAccessError> {
     let conn = self.main_sql_db.acquire().await;
     if conn.is_err() {
@@ -200,7 +625,9 @@ impl PostRepository {
     let db_result = sqlx::query(
       format!(
       r#"
-      SELECT id, slug, title, poster_id, short_description, description, tag_ids, published_at, is_published
+      SELECT id, slug, title, poster_id, short_description, description, tag_ids, published_at, is_published, expires_at, is_archived,
+        moderation_status, moderation_reason, similar_post_id,
+        wilaya_code, contract_type, remote_policy, salary_min, salary_max, salary_currency, salary_period
       FROM post
       WHERE id IN ({}) AND is_deleted = 0
       "#,
@@ -249,9 +676,16 @@ impl PostRepository {
         "poster_id": row.get::<u32, _>("poster_id"),
         "short_description": row.get::<String, _>("short_description"),
         "description": row.get::<String, _>("description"),
+        "description_html": render_markdown(row.get::<&str, _>("description")),
         "tag_ids": tag_ids,
         "published_at": row.get::<String, _>("published_at"),
+        "attributes": get_post_attributes_from_row(&row),
         "is_published": row.get::<bool, _>("is_published"),
+        "expires_at": row.get::<String, _>("expires_at"),
+        "is_archived": row.get::<bool, _>("is_archived"),
+        "moderation_status": row.get::<String, _>("moderation_status"),
+        "moderation_reason": row.get::<String, _>("moderation_reason"),
+        "similar_post_id": row.get::<Option<u32>, _>("similar_post_id"),
       });
 
       let post = serde_json::from_value::<Post>(json_post);
@@ -267,6 +701,67 @@ impl PostRepository {
     Ok(posts)
   }
 
+  pub async fn get_many_post_spam_scores_by_ids(
+    &self,
+    ids: Vec<u32>,
+  ) -> Result<Vec<PostSpamScore>, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      format!(
+        r#"
+      SELECT id, spam_score, spam_rules
+      FROM post
+      WHERE id IN ({})
+      "#,
+        ids
+          .iter()
+          .map(|id| id.to_string())
+          .collect::<Vec<String>>()
+          .join(",")
+      )
+      .as_str(),
+    )
+    .fetch_all(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting many post spam scores by ids: {:?}",
+        db_result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+    let db_result = db_result.unwrap();
+
+    let mut post_spam_scores = vec![];
+    for row in db_result {
+      let matched_rules =
+        serde_json::from_str::<Vec<SpamRuleMatch>>(row.get::<&str, _>("spam_rules"));
+      if matched_rules.is_err() {
+        tracing::error!(
+          "Error while parsing the spam rules of post {}: {:?}",
+          row.get::<u32, _>("id"),
+          matched_rules.err()
+        );
+        return Err(DataAccessError::InternalError);
+      }
+
+      post_spam_scores.push(PostSpamScore {
+        post_id: row.get::<u32, _>("id"),
+        score: row.get::<f64, _>("spam_score"),
+        matched_rules: matched_rules.unwrap(),
+      });
+    }
+
+    Ok(post_spam_scores)
+  }
+
   pub async fn get_one_post_by_id(&self, id: u32) -> Result<Post, DataAccessError> {
     let conn = self.main_sql_db.acquire().await;
     if conn.is_err() {
@@ -278,7 +773,9 @@ impl PostRepository {
     // @TODO-ZM: use * instead of listing all the fields?
     let db_result = sqlx::query(
       r#"
-      SELECT id, slug, title, poster_id, short_description, description, tag_ids, published_at, is_published
+      SELECT id, slug, title, poster_id, short_description, description, tag_ids, published_at, is_published, expires_at, is_archived,
+        moderation_status, moderation_reason, similar_post_id,
+        wilaya_code, contract_type, remote_policy, salary_min, salary_max, salary_currency, salary_period
       FROM post
       WHERE id = $1 AND is_deleted = 0
       "#,
@@ -327,9 +824,16 @@ impl PostRepository {
       "poster_id": db_result.get::<u32, _>("poster_id"),
       "short_description": db_result.get::<String, _>("short_description"),
       "description": db_result.get::<String, _>("description"),
+      "description_html": render_markdown(db_result.get::<&str, _>("description")),
       "tag_ids": tag_ids,
       "published_at": db_result.get::<String, _>("published_at"),
+      "attributes": get_post_attributes_from_row(&db_result),
       "is_published": db_result.get::<bool, _>("is_published"),
+      "expires_at": db_result.get::<String, _>("expires_at"),
+      "is_archived": db_result.get::<bool, _>("is_archived"),
+      "moderation_status": db_result.get::<String, _>("moderation_status"),
+      "moderation_reason": db_result.get::<String, _>("moderation_reason"),
+      "similar_post_id": db_result.get::<Option<u32>, _>("similar_post_id"),
     });
 
     let post = serde_json::from_value::<Post>(json_post);
@@ -342,18 +846,29 @@ impl PostRepository {
     Ok(post)
   }
 
-  pub async fn create_one_post(&self, post: &DBPost) -> Result<u32, DataAccessError> {
-    let conn = self.main_sql_db.acquire().await;
-    if conn.is_err() {
-      tracing::error!("Error while getting sql connection: {:?}", conn);
+  // the revision gets the id of the created post
+  pub async fn create_one_post(
+    &self,
+    post: &DBPost,
+    spam_score: &SpamScore,
+    post_revision: &DBPostRevision,
+  ) -> Result<u32, DataAccessError> {
+    let tx = self.main_sql_db.begin().await;
+    if tx.is_err() {
+      tracing::error!("Error while starting sql transaction: {:?}", tx);
       return Err(DataAccessError::InternalError);
     }
-    let mut conn = conn.unwrap();
+    let mut tx = tx.unwrap();
+    let (salary_min, salary_max, salary_currency, salary_period) =
+      get_salary_columns(&post.attributes);
+    let tokens = get_content_tokens(&post.title, &post.description);
 
     let db_result = sqlx::query(
       r#"
-      INSERT INTO post (slug, title, poster_id, short_description, description, tag_ids, published_at, created_at)
-      VALUES ($1, $2, $3, $4, $5, $6, $7, strftime('%Y-%m-%dT%H:%M:%S.%fZ', 'now'))
+      INSERT INTO post (slug, title, poster_id, short_description, description, tag_ids, published_at, expires_at,
+        wilaya_code, contract_type, remote_policy, salary_min, salary_max, salary_currency, salary_period, moderation_status,
+        similar_post_id, content_hash, minhash, spam_score, spam_rules, created_at)
+      VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, strftime('%Y-%m-%dT%H:%M:%S.%fZ', 'now'))
       "#,
     )
     .bind(&post.slug)
@@ -363,7 +878,21 @@ impl PostRepository {
     .bind(&post.description)
     .bind(&post.tag_ids.iter().map(|id| id.to_string()).collect::<Vec<String>>().join(","))
     .bind(&post.published_at)
-    .execute(&mut *conn)
+    .bind(&post.expires_at)
+    .bind(post.attributes.wilaya_code)
+    .bind(post.attributes.contract_type.as_ref().map(|contract_type| contract_type.to_string()))
+    .bind(post.attributes.remote_policy.as_ref().map(|remote_policy| remote_policy.to_string()))
+    .bind(salary_min)
+    .bind(salary_max)
+    .bind(salary_currency)
+    .bind(salary_period)
+    .bind(post.moderation_status.to_string())
+    .bind(post.similar_post_id)
+    .bind(get_content_hash(&tokens))
+    .bind(get_minhash(&tokens))
+    .bind(spam_score.score)
+    .bind(json!(spam_score.matched_rules).to_string())
+    .execute(&mut *tx)
     .await;
 
     if db_result.is_err() {
@@ -372,49 +901,204 @@ impl PostRepository {
     }
 
     let id = db_result.unwrap().last_insert_rowid() as u32;
+
+    let post_revision_id = insert_one_post_revision(
+      &mut tx,
+      &DBPostRevision {
+        post_id: id,
+        ..post_revision.clone()
+      },
+    )
+    .await;
+    if post_revision_id.is_err() {
+      return Err(DataAccessError::InternalError);
+    }
+
+    let commit_result = tx.commit().await;
+    if commit_result.is_err() {
+      tracing::error!("Error while committing post creation: {:?}", commit_result);
+      return Err(DataAccessError::InternalError);
+    }
+
     Ok(id)
   }
 
-  pub async fn get_published_post_count(&self) -> Result<u32, DataAccessError> {
-    let conn = self.main_sql_db.acquire().await;
-    if conn.is_err() {
-      tracing::error!("Error while getting sql connection: {:?}", conn);
+  // the revision is only kept if the post is updated
+  pub async fn update_one_post(
+    &self,
+    post: &Post,
+    spam_score: &SpamScore,
+    post_revision: &DBPostRevision,
+  ) -> Result<(), DataAccessError> {
+    let tx = self.main_sql_db.begin().await;
+    if tx.is_err() {
+      tracing::error!("Error while starting sql transaction: {:?}", tx);
       return Err(DataAccessError::InternalError);
     }
-    let mut conn = conn.unwrap();
+    let mut tx = tx.unwrap();
+    let (salary_min, salary_max, salary_currency, salary_period) =
+      get_salary_columns(&post.attributes);
+    let tokens = get_content_tokens(&post.title, &post.description);
 
     let db_result = sqlx::query(
       r#"
-      SELECT COUNT(*) as count
-      FROM post
-      WHERE is_published = 1 AND is_deleted = 0
+      UPDATE post
+      SET slug = $1, title = $2, short_description = $3, description = $4, tag_ids = $5,
+        wilaya_code = $6, contract_type = $7, remote_policy = $8,
+        salary_min = $9, salary_max = $10, salary_currency = $11, salary_period = $12,
+        content_hash = $13, minhash = $14, moderation_status = $15, spam_score = $16, spam_rules = $17,
+        similar_post_id = $18
+      WHERE id = $19 AND is_deleted = 0
       "#,
     )
-    .fetch_one(&mut *conn)
+    .bind(&post.slug)
+    .bind(&post.title)
+    .bind(&post.short_description)
+    .bind(&post.description)
+    .bind(
+      &post
+        .tag_ids
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<String>>()
+        .join(","),
+    )
+    .bind(post.attributes.wilaya_code)
+    .bind(
+      post
+        .attributes
+        .contract_type
+        .as_ref()
+        .map(|contract_type| contract_type.to_string()),
+    )
+    .bind(
+      post
+        .attributes
+        .remote_policy
+        .as_ref()
+        .map(|remote_policy| remote_policy.to_string()),
+    )
+    .bind(salary_min)
+    .bind(salary_max)
+    .bind(salary_currency)
+    .bind(salary_period)
+    .bind(get_content_hash(&tokens))
+    .bind(get_minhash(&tokens))
+    .bind(post.moderation_status.to_string())
+    .bind(spam_score.score)
+    .bind(json!(spam_score.matched_rules).to_string())
+    .bind(post.similar_post_id)
+    .bind(post.id)
+    .execute(&mut *tx)
     .await;
 
     if db_result.is_err() {
-      tracing::error!(
-        "Error while getting published post count: {:?}",
-        db_result.err()
-      );
+      tracing::error!("Error while updating one post: {:?}", db_result);
       return Err(DataAccessError::InternalError);
     }
 
-    let db_result = db_result.unwrap();
-    let count = db_result.get::<i64, _>("count") as u32;
+    if db_result.unwrap().rows_affected() == 0 {
+      return Err(DataAccessError::NotFound);
+    }
 
-    Ok(count)
-  }
+    let post_revision_id = insert_one_post_revision(&mut tx, post_revision).await;
+    if post_revision_id.is_err() {
+      return Err(DataAccessError::InternalError);
+    }
 
-  pub async fn delete_one_post_by_id(&self, id: u32) -> Result<(), DataAccessError> {
-    let conn = self.main_sql_db.acquire().await;
+    let commit_result = tx.commit().await;
+    if commit_result.is_err() {
+      tracing::error!("Error while committing post update: {:?}", commit_result);
+      return Err(DataAccessError::InternalError);
+    }
+
+    Ok(())
+  }
+
+  // counted over all the published posts matching the filters, not only a page of them
+  pub async fn get_published_post_facets(
+    &self,
+    filters: &PostFeedFilters,
+  ) -> Result<PostFacets, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let mut query_builder = QueryBuilder::<Sqlite>::new(
+      r#"
+      WITH matched_post AS MATERIALIZED (
+        SELECT post.id, post.tag_ids, account.type AS poster_type, post.wilaya_code, post.contract_type
+        FROM post
+        JOIN account ON account.id = post.poster_id
+        WHERE post.is_published = 1 AND post.is_deleted = 0 AND post.is_archived = 0
+          AND substr(post.published_at, 1, 19) <= strftime('%Y-%m-%dT%H:%M:%S', 'now')
+          AND post.moderation_status = 'approved'"#,
+    );
+    push_post_feed_filters(&mut query_builder, filters);
+    query_builder.push(")");
+    push_post_facets_query(&mut query_builder);
+
+    let db_result = query_builder.build().fetch_all(&mut *conn).await;
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting published post facets: {:?}",
+        db_result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+
+    Ok(get_post_facets_from_rows(&db_result.unwrap()))
+  }
+
+  pub async fn get_published_post_count(&self) -> Result<u32, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
     if conn.is_err() {
       tracing::error!("Error while getting sql connection: {:?}", conn);
       return Err(DataAccessError::InternalError);
     }
     let mut conn = conn.unwrap();
 
+    let db_result = sqlx::query(
+      r#"
+      SELECT COUNT(*) as count
+      FROM post
+      WHERE is_published = 1 AND is_deleted = 0 AND is_archived = 0
+        AND substr(published_at, 1, 19) <= strftime('%Y-%m-%dT%H:%M:%S', 'now')
+        AND moderation_status = 'approved'
+      "#,
+    )
+    .fetch_one(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting published post count: {:?}",
+        db_result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+
+    let db_result = db_result.unwrap();
+    let count = db_result.get::<i64, _>("count") as u32;
+
+    Ok(count)
+  }
+
+  pub async fn delete_one_post_by_id(
+    &self,
+    id: u32,
+    post_revision: &DBPostRevision,
+  ) -> Result<(), DataAccessError> {
+    let tx = self.main_sql_db.begin().await;
+    if tx.is_err() {
+      tracing::error!("Error while starting sql transaction: {:?}", tx);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut tx = tx.unwrap();
+
     let db_result = sqlx::query(
       r#"
       UPDATE post
@@ -423,7 +1107,7 @@ impl PostRepository {
       "#,
     )
     .bind(id)
-    .execute(&mut *conn)
+    .execute(&mut *tx)
     .await;
 
     if db_result.is_err() {
@@ -431,10 +1115,78 @@ impl PostRepository {
       return Err(DataAccessError::InternalError);
     }
 
+    let post_revision_id = insert_one_post_revision(&mut tx, post_revision).await;
+    if post_revision_id.is_err() {
+      return Err(DataAccessError::InternalError);
+    }
+
+    let commit_result = tx.commit().await;
+    if commit_result.is_err() {
+      tracing::error!("Error while committing post deletion: {:?}", commit_result);
+      return Err(DataAccessError::InternalError);
+    }
+
+    Ok(())
+  }
+
+  // sets the publishing date, and holds the post for moderation when it was found to be a near duplicate
+  pub async fn publish_one_post(
+    &self,
+    post: &Post,
+    post_revision: &DBPostRevision,
+  ) -> Result<(), DataAccessError> {
+    let tx = self.main_sql_db.begin().await;
+    if tx.is_err() {
+      tracing::error!("Error while starting sql transaction: {:?}", tx);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut tx = tx.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      UPDATE post
+      SET published_at = $1, moderation_status = $2, similar_post_id = $3
+      WHERE id = $4
+      "#,
+    )
+    .bind(&post.published_at)
+    .bind(post.moderation_status.to_string())
+    .bind(post.similar_post_id)
+    .bind(post.id)
+    .execute(&mut *tx)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while publishing one post: {:?}", db_result);
+      return Err(DataAccessError::InternalError);
+    }
+
+    let post_revision_id = insert_one_post_revision(&mut tx, post_revision).await;
+    if post_revision_id.is_err() {
+      return Err(DataAccessError::InternalError);
+    }
+
+    let commit_result = tx.commit().await;
+    if commit_result.is_err() {
+      tracing::error!(
+        "Error while committing post publishing: {:?}",
+        commit_result
+      );
+      return Err(DataAccessError::InternalError);
+    }
+
     Ok(())
   }
 
-  pub async fn publish_one_post_by_id(&self, id: u32) -> Result<(), DataAccessError> {
+  // exact duplicates win over near ones, archived and deleted posts are left out,
+  // unconfirmed posts are kept as they can still be confirmed, and the post being checked, if any, is skipped
+  pub async fn get_one_post_duplicate(
+    &self,
+    post_id: Option<u32>,
+    poster_id: Option<u32>,
+    title: &String,
+    description: &String,
+  ) -> Result<Option<PostDuplicate>, DataAccessError> {
     let conn = self.main_sql_db.acquire().await;
     if conn.is_err() {
       tracing::error!("Error while getting sql connection: {:?}", conn);
@@ -442,10 +1194,314 @@ impl PostRepository {
     }
     let mut conn = conn.unwrap();
 
+    let tokens = get_content_tokens(title, description);
+    let content_hash = get_content_hash(&tokens);
+    let minhash = get_minhash(&tokens);
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT id, content_hash, minhash
+      FROM post
+      WHERE is_deleted = 0 AND is_archived = 0 AND minhash <> '' AND ($1 IS NULL OR id <> $1)
+        AND created_at >= strftime('%Y-%m-%dT%H:%M:%S', 'now', '-' || (CASE WHEN poster_id = $2 THEN $3 ELSE $4 END) || ' days')
+      ORDER BY id DESC
+      LIMIT $5
+      "#,
+    )
+    .bind(post_id)
+    .bind(poster_id)
+    .bind(DUPLICATE_POSTER_CORPUS_DAYS)
+    .bind(DUPLICATE_CORPUS_DAYS)
+    .bind(MAX_DUPLICATE_CANDIDATE_COUNT)
+    .fetch_all(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting one post duplicate: {:?}",
+        db_result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+    let db_result = db_result.unwrap();
+
+    let exact_duplicate = db_result
+      .iter()
+      .find(|row| row.get::<String, _>("content_hash") == content_hash);
+    if let Some(row) = exact_duplicate {
+      return Ok(Some(PostDuplicate::Exact {
+        post_id: row.get::<u32, _>("id"),
+      }));
+    }
+
+    if tokens.len() < NEAR_DUPLICATE_MIN_TOKEN_COUNT {
+      return Ok(None);
+    }
+
+    let near_duplicate = db_result.iter().find(|row| {
+      get_minhash_similarity(&row.get::<String, _>("minhash"), &minhash)
+        .is_some_and(|similarity| similarity >= NEAR_DUPLICATE_MIN_SIMILARITY)
+    });
+
+    Ok(near_duplicate.map(|row| PostDuplicate::Near {
+      post_id: row.get::<u32, _>("id"),
+    }))
+  }
+
+  // confirmed posts with the given moderation status, oldest first so the queue is worked in order
+  pub async fn get_many_posts_by_moderation_status(
+    &self,
+    moderation_status: &PostModerationStatus,
+    pagination: &DBPaginationQuery,
+  ) -> Result<Vec<Post>, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT id, slug, title, poster_id, short_description, description, tag_ids, published_at, is_published, expires_at, is_archived,
+        moderation_status, moderation_reason, similar_post_id,
+        wilaya_code, contract_type, remote_policy, salary_min, salary_max, salary_currency, salary_period
+      FROM post
+      WHERE is_published = 1 AND is_deleted = 0 AND moderation_status = $1
+      ORDER BY published_at ASC, id ASC
+      LIMIT $2 OFFSET $3
+      "#,
+    )
+    .bind(moderation_status.to_string())
+    .bind(pagination.limit)
+    .bind(pagination.start)
+    .fetch_all(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting many posts by moderation status: {:?}",
+        db_result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+    let db_result = db_result.unwrap();
+
+    let mut posts = vec![];
+    for row in db_result {
+      posts.push(get_post_from_row(&row)?);
+    }
+
+    Ok(posts)
+  }
+
+  pub async fn moderate_one_post_by_id(
+    &self,
+    id: u32,
+    moderation_status: &PostModerationStatus,
+    moderation_reason: &String,
+    post_revision: &DBPostRevision,
+  ) -> Result<(), DataAccessError> {
+    let tx = self.main_sql_db.begin().await;
+    if tx.is_err() {
+      tracing::error!("Error while starting sql transaction: {:?}", tx);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut tx = tx.unwrap();
+
     let db_result = sqlx::query(
       r#"
       UPDATE post
-      SET published_at = strftime('%Y-%m-%dT%H:%M:%S.%fZ', 'now')
+      SET moderation_status = $1, moderation_reason = $2, moderated_at = strftime('%Y-%m-%dT%H:%M:%S.%fZ', 'now')
+      WHERE id = $3 AND is_deleted = 0
+      "#,
+    )
+    .bind(moderation_status.to_string())
+    .bind(moderation_reason)
+    .bind(id)
+    .execute(&mut *tx)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while moderating one post: {:?}", db_result);
+      return Err(DataAccessError::InternalError);
+    }
+
+    if db_result.unwrap().rows_affected() == 0 {
+      return Err(DataAccessError::NotFound);
+    }
+
+    let post_revision_id = insert_one_post_revision(&mut tx, post_revision).await;
+    if post_revision_id.is_err() {
+      return Err(DataAccessError::InternalError);
+    }
+
+    let commit_result = tx.commit().await;
+    if commit_result.is_err() {
+      tracing::error!(
+        "Error while committing post moderation: {:?}",
+        commit_result
+      );
+      return Err(DataAccessError::InternalError);
+    }
+
+    Ok(())
+  }
+
+  // published posts past their expiry date, that are not archived yet
+  pub async fn get_many_expired_posts(&self, limit: u32) -> Result<Vec<Post>, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT id, slug, title, poster_id, short_description, description, tag_ids, published_at, is_published, expires_at, is_archived,
+        moderation_status, moderation_reason, similar_post_id,
+        wilaya_code, contract_type, remote_policy, salary_min, salary_max, salary_currency, salary_period
+      FROM post
+      WHERE is_published = 1 AND is_deleted = 0 AND is_archived = 0
+        AND expires_at <> '' AND expires_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+      ORDER BY expires_at ASC
+      LIMIT $1
+      "#,
+    )
+    .bind(limit)
+    .fetch_all(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting many expired posts: {:?}",
+        db_result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+    let db_result = db_result.unwrap();
+
+    let mut posts = vec![];
+    for row in db_result {
+      posts.push(get_post_from_row(&row)?);
+    }
+
+    Ok(posts)
+  }
+
+  // published posts expiring within `days`, whose posters were not reminded yet
+  pub async fn get_many_expiring_posts(
+    &self,
+    days: i64,
+    limit: u32,
+  ) -> Result<Vec<Post>, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT id, slug, title, poster_id, short_description, description, tag_ids, published_at, is_published, expires_at, is_archived,
+        moderation_status, moderation_reason, similar_post_id,
+        wilaya_code, contract_type, remote_policy, salary_min, salary_max, salary_currency, salary_period
+      FROM post
+      WHERE is_published = 1 AND is_deleted = 0 AND is_archived = 0 AND expiry_reminded_at = ''
+        AND substr(published_at, 1, 19) <= strftime('%Y-%m-%dT%H:%M:%S', 'now')
+        AND moderation_status = 'approved'
+        AND expires_at <> '' AND expires_at <= strftime('%Y-%m-%dT%H:%M:%fZ', 'now', $1)
+      ORDER BY expires_at ASC
+      LIMIT $2
+      "#,
+    )
+    .bind(format!("+{} days", days))
+    .bind(limit)
+    .fetch_all(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting many expiring posts: {:?}",
+        db_result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+    let db_result = db_result.unwrap();
+
+    let mut posts = vec![];
+    for row in db_result {
+      posts.push(get_post_from_row(&row)?);
+    }
+
+    Ok(posts)
+  }
+
+  // one revision per archived post
+  pub async fn archive_many_posts(
+    &self,
+    post_revisions: &Vec<DBPostRevision>,
+  ) -> Result<(), DataAccessError> {
+    let tx = self.main_sql_db.begin().await;
+    if tx.is_err() {
+      tracing::error!("Error while starting sql transaction: {:?}", tx);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut tx = tx.unwrap();
+
+    let db_result = sqlx::query(
+      format!(
+        r#"
+      UPDATE post
+      SET archived_at = strftime('%Y-%m-%dT%H:%M:%S.%fZ', 'now')
+      WHERE id IN ({})
+      "#,
+        post_revisions
+          .iter()
+          .map(|post_revision| post_revision.post_id.to_string())
+          .collect::<Vec<String>>()
+          .join(",")
+      )
+      .as_str(),
+    )
+    .execute(&mut *tx)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while archiving many posts: {:?}", db_result);
+      return Err(DataAccessError::InternalError);
+    }
+
+    for post_revision in post_revisions {
+      let post_revision_id = insert_one_post_revision(&mut tx, post_revision).await;
+      if post_revision_id.is_err() {
+        return Err(DataAccessError::InternalError);
+      }
+    }
+
+    let commit_result = tx.commit().await;
+    if commit_result.is_err() {
+      tracing::error!("Error while committing post archiving: {:?}", commit_result);
+      return Err(DataAccessError::InternalError);
+    }
+
+    Ok(())
+  }
+
+  pub async fn remind_one_post_of_expiry_by_id(&self, id: u32) -> Result<(), DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      UPDATE post
+      SET expiry_reminded_at = strftime('%Y-%m-%dT%H:%M:%S.%fZ', 'now')
       WHERE id = $1
       "#,
     )
@@ -454,7 +1510,55 @@ impl PostRepository {
     .await;
 
     if db_result.is_err() {
-      tracing::error!("Error while publishing one post: {:?}", db_result);
+      tracing::error!(
+        "Error while reminding one post of expiry by id: {:?}",
+        db_result
+      );
+      return Err(DataAccessError::InternalError);
+    }
+
+    Ok(())
+  }
+
+  // also un-archives the post, and lets its poster be reminded again
+  pub async fn extend_one_post_by_id(
+    &self,
+    id: u32,
+    expires_at: &String,
+    post_revision: &DBPostRevision,
+  ) -> Result<(), DataAccessError> {
+    let tx = self.main_sql_db.begin().await;
+    if tx.is_err() {
+      tracing::error!("Error while starting sql transaction: {:?}", tx);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut tx = tx.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      UPDATE post
+      SET expires_at = $1, expiry_reminded_at = '', archived_at = ''
+      WHERE id = $2 AND is_deleted = 0
+      "#,
+    )
+    .bind(expires_at)
+    .bind(id)
+    .execute(&mut *tx)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while extending one post by id: {:?}", db_result);
+      return Err(DataAccessError::InternalError);
+    }
+
+    let post_revision_id = insert_one_post_revision(&mut tx, post_revision).await;
+    if post_revision_id.is_err() {
+      return Err(DataAccessError::InternalError);
+    }
+
+    let commit_result = tx.commit().await;
+    if commit_result.is_err() {
+      tracing::error!("Error while committing post extension: {:?}", commit_result);
       return Err(DataAccessError::InternalError);
     }
 
@@ -0,0 +1,172 @@
+use axum::{
+  extract::{ConnectInfo, Path, State},
+  response::IntoResponse,
+  Json, Router,
+};
+use hyper::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+use std::net::SocketAddr;
+
+use super::model::{DBPostReport, PostReportReason};
+use crate::{
+  _entry::state::AppState,
+  _utils::{
+    error::{DataAccessError, SecurityError},
+    ip_hash::get_ip_hash,
+  },
+  post::model::{Post, PostModerationStatus, PostTrait},
+  post_revision::model::{PostRevisionReason, PostSnapshotTrait, SYSTEM_AUTHOR_ID},
+  security::service::RateLimitConstraint,
+  task::model::{DBTask, TaskName, TaskStatus, TaskType},
+};
+
+const MAX_REPORT_MESSAGE_LENGTH: usize = 2000;
+
+#[derive(Deserialize)]
+pub struct CreateOnePostReportBody {
+  reason: PostReportReason,
+  #[serde(default)]
+  message: String,
+}
+
+pub async fn create_one_post_report(
+  ConnectInfo(ip): ConnectInfo<SocketAddr>,
+  State(app_state): State<AppState>,
+  Path(post_id): Path<u32>,
+  Json(body): Json<CreateOnePostReportBody>,
+) -> impl IntoResponse {
+  let message = body.message.trim().to_string();
+  if message.chars().count() > MAX_REPORT_MESSAGE_LENGTH {
+    return StatusCode::BAD_REQUEST.into_response();
+  }
+
+  // @TODO-ZM: write a macro for this
+  match app_state.security_service.rate_limit(vec![
+    RateLimitConstraint {
+      id: format!("create_one_post_report-1-{}", ip.ip()),
+      max_requests: 5,
+      duration_ms: 3_600_000,
+    },
+    RateLimitConstraint {
+      id: format!("create_one_post_report-2-{}", ip.ip()),
+      max_requests: 20,
+      duration_ms: 86_400_000,
+    },
+  ]) {
+    Ok(_) => {}
+    Err(SecurityError::InternalError) => {
+      // @TODO-ZM: log error reason
+      return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    Err(SecurityError::RateLimitError) => {
+      return StatusCode::TOO_MANY_REQUESTS.into_response();
+    }
+  }
+
+  let post = app_state.post_repository.get_one_post_by_id(post_id).await;
+  if !post.is_ok() {
+    match post {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let post = post.unwrap();
+
+  // only what candidates can see can be reported
+  if !post.is_live() {
+    return StatusCode::NOT_FOUND.into_response();
+  }
+
+  let config = app_state.config_service.get_config();
+
+  let post_report_id = app_state
+    .post_report_repository
+    .create_one_post_report(
+      &DBPostReport {
+        post_id,
+        reason: body.reason,
+        message,
+      },
+      &get_ip_hash(&ip.ip(), "post_report", &config.ip_hash_secret),
+    )
+    .await;
+  if !post_report_id.is_ok() {
+    match post_report_id {
+      Err(DataAccessError::AlreadyExists) => {
+        return StatusCode::CONFLICT.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let post_report_id = post_report_id.unwrap();
+
+  let open_report_count = app_state
+    .post_report_repository
+    .get_open_post_report_count_by_post_id(post_id)
+    .await;
+  if !open_report_count.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let open_report_count = open_report_count.unwrap();
+
+  // enough reports send the post back to the moderation queue, until an admin reviews it
+  if open_report_count >= config.post_report_threshold {
+    let hidden_post = Post {
+      moderation_status: PostModerationStatus::Pending,
+      moderation_reason: "".to_string(),
+      ..post
+    };
+
+    let moderation_result = app_state
+      .post_repository
+      .moderate_one_post_by_id(
+        post_id,
+        &hidden_post.moderation_status,
+        &hidden_post.moderation_reason,
+        &hidden_post.to_db_post_revision(SYSTEM_AUTHOR_ID, PostRevisionReason::Reported),
+      )
+      .await;
+    if !moderation_result.is_ok() {
+      // @TODO-ZM: log error reason
+      return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+
+    let task_id = app_state
+      .task_repository
+      .create_one_task(DBTask {
+        name: TaskName::UndoIndexing {
+          model_name: "post".to_string(),
+          model_id: post_id,
+        },
+        status: TaskStatus::Pending,
+        r#type: TaskType::Automated,
+      })
+      .await;
+    if !task_id.is_ok() {
+      // @TODO-ZM: log error reason
+      return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+  }
+
+  Json(json!({
+      "post_report_id": post_report_id,
+  }))
+  .into_response()
+}
+
+pub fn create_post_report_router() -> Router<AppState> {
+  Router::new().route(
+    "/:post_id/reports",
+    axum::routing::post(create_one_post_report),
+  )
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+use utility_types::omit;
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PostReportReason {
+  // eg: asks candidates for payment
+  Scam,
+  Spam,
+  Misleading,
+  Discriminatory,
+  Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[omit(DBPostReport, [id, created_at, reviewed_at], [Debug, Serialize, Deserialize, Clone])]
+pub struct PostReport {
+  pub id: u32,
+  pub post_id: u32,
+  pub reason: PostReportReason,
+  pub message: String,
+  pub created_at: String,
+  // set once an admin took a moderation decision on the post
+  pub reviewed_at: String,
+}
+
+// one row of the report dashboard, only open reports are counted
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReportedPost {
+  pub post_id: u32,
+  pub report_count: u32,
+  pub last_reported_at: String,
+}
@@ -0,0 +1,232 @@
+use serde_json::json;
+use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
+use std::sync::Arc;
+
+use super::model::{DBPostReport, PostReport, ReportedPost};
+use crate::_utils::{error::DataAccessError, query::DBPaginationQuery};
+
+fn get_post_report_from_row(row: &SqliteRow) -> Result<PostReport, DataAccessError> {
+  let json_post_report = json!({
+    "id": row.get::<u32, _>("id"),
+    "post_id": row.get::<u32, _>("post_id"),
+    "reason": row.get::<String, _>("reason"),
+    "message": row.get::<String, _>("message"),
+    "created_at": row.get::<String, _>("created_at"),
+    "reviewed_at": row.get::<String, _>("reviewed_at"),
+  });
+
+  let post_report = serde_json::from_value::<PostReport>(json_post_report);
+  if post_report.is_err() {
+    tracing::error!(
+      "Error while deserializing post report: {:?}",
+      post_report.err()
+    );
+    return Err(DataAccessError::InternalError);
+  }
+
+  Ok(post_report.unwrap())
+}
+
+pub struct PostReportRepository {
+  main_sql_db: Arc<Pool<Sqlite>>,
+}
+
+impl PostReportRepository {
+  pub fn new(main_sql_db: Arc<Pool<Sqlite>>) -> Self {
+    Self { main_sql_db }
+  }
+
+  // a reporter can only report a post once, they're only known by a hash of their ip
+  pub async fn create_one_post_report(
+    &self,
+    post_report: &DBPostReport,
+    reporter_hash: &String,
+  ) -> Result<u32, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      INSERT OR IGNORE INTO post_report (post_id, reason, message, reporter_hash, created_at)
+      VALUES ($1, $2, $3, $4, strftime('%Y-%m-%dT%H:%M:%S.%fZ', 'now'))
+      "#,
+    )
+    .bind(post_report.post_id)
+    .bind(post_report.reason.to_string())
+    .bind(&post_report.message)
+    .bind(reporter_hash)
+    .execute(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while creating one post report: {:?}", db_result);
+      return Err(DataAccessError::InternalError);
+    }
+    let db_result = db_result.unwrap();
+
+    if db_result.rows_affected() == 0 {
+      return Err(DataAccessError::AlreadyExists);
+    }
+
+    Ok(db_result.last_insert_rowid() as u32)
+  }
+
+  pub async fn get_open_post_report_count_by_post_id(
+    &self,
+    post_id: u32,
+  ) -> Result<u32, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT COUNT(*) AS count
+      FROM post_report
+      WHERE post_id = $1 AND reviewed_at = ''
+      "#,
+    )
+    .bind(post_id)
+    .fetch_one(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting open post report count: {:?}",
+        db_result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+
+    Ok(db_result.unwrap().get::<u32, _>("count"))
+  }
+
+  // most reported first
+  pub async fn get_many_reported_posts(
+    &self,
+    pagination: &DBPaginationQuery,
+  ) -> Result<Vec<ReportedPost>, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT post_id, COUNT(*) AS report_count, MAX(created_at) AS last_reported_at
+      FROM post_report
+      WHERE reviewed_at = ''
+      GROUP BY post_id
+      ORDER BY report_count DESC, last_reported_at DESC
+      LIMIT $1 OFFSET $2
+      "#,
+    )
+    .bind(pagination.limit)
+    .bind(pagination.start)
+    .fetch_all(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting many reported posts: {:?}",
+        db_result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+
+    let reported_posts = db_result
+      .unwrap()
+      .iter()
+      .map(|row| ReportedPost {
+        post_id: row.get::<u32, _>("post_id"),
+        report_count: row.get::<u32, _>("report_count"),
+        last_reported_at: row.get::<String, _>("last_reported_at"),
+      })
+      .collect::<Vec<ReportedPost>>();
+
+    Ok(reported_posts)
+  }
+
+  // newest first, reviewed ones included
+  pub async fn get_many_post_reports_by_post_id(
+    &self,
+    post_id: u32,
+    pagination: &DBPaginationQuery,
+  ) -> Result<Vec<PostReport>, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT id, post_id, reason, message, created_at, reviewed_at
+      FROM post_report
+      WHERE post_id = $1
+      ORDER BY id DESC
+      LIMIT $2 OFFSET $3
+      "#,
+    )
+    .bind(post_id)
+    .bind(pagination.limit)
+    .bind(pagination.start)
+    .fetch_all(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting many post reports by post id: {:?}",
+        db_result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+
+    let mut post_reports = vec![];
+    for row in db_result.unwrap() {
+      post_reports.push(get_post_report_from_row(&row)?);
+    }
+
+    Ok(post_reports)
+  }
+
+  pub async fn review_many_post_reports_by_post_id(
+    &self,
+    post_id: u32,
+  ) -> Result<(), DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      UPDATE post_report
+      SET reviewed_at = strftime('%Y-%m-%dT%H:%M:%S.%fZ', 'now')
+      WHERE post_id = $1 AND reviewed_at = ''
+      "#,
+    )
+    .bind(post_id)
+    .execute(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while reviewing post reports: {:?}", db_result);
+      return Err(DataAccessError::InternalError);
+    }
+
+    Ok(())
+  }
+}
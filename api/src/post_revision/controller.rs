@@ -0,0 +1,352 @@
+use axum::{
+  extract::{ConnectInfo, Path, Query, State},
+  response::IntoResponse,
+  Json, Router,
+};
+use hyper::StatusCode;
+use serde_json::json;
+use std::net::SocketAddr;
+
+use super::model::{PostRevisionReason, PostRevisionTrait, PostSnapshotTrait};
+use crate::{
+  _entry::state::AppState,
+  _utils::{
+    error::{DataAccessError, SecurityError},
+    markdown::render_markdown,
+    query::PostRevisionDiffQuery,
+  },
+  auth::service::{ScopedToken, ScopedTokenTrait},
+  post::{
+    controller::get_edited_moderation_status,
+    model::{PartialPost, PartialPostTrait, PostDuplicate, PostTrait},
+  },
+  security::service::RateLimitConstraint,
+  spam::model::SpamVerdict,
+  task::model::{DBTask, TaskName, TaskStatus, TaskType},
+};
+
+pub async fn get_many_post_revisions(
+  State(app_state): State<AppState>,
+  scoped_token: ScopedToken,
+  Path(post_id): Path<u32>,
+) -> impl IntoResponse {
+  let post = app_state.post_repository.get_one_post_by_id(post_id).await;
+  if !post.is_ok() {
+    match post {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let post = post.unwrap();
+
+  // admins read the history to moderate the post
+  if post.poster_id != scoped_token.id && !scoped_token.is_admin() {
+    return StatusCode::FORBIDDEN.into_response();
+  }
+
+  let post_revisions = app_state
+    .post_revision_repository
+    .get_many_post_revisions_by_post_id(post_id)
+    .await;
+  if !post_revisions.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let post_revisions = post_revisions.unwrap();
+
+  Json(json!({
+      "revisions": post_revisions,
+  }))
+  .into_response()
+}
+
+pub async fn get_post_revision_diff(
+  State(app_state): State<AppState>,
+  scoped_token: ScopedToken,
+  Path(post_id): Path<u32>,
+  url_query: Query<PostRevisionDiffQuery>,
+) -> impl IntoResponse {
+  let post = app_state.post_repository.get_one_post_by_id(post_id).await;
+  if !post.is_ok() {
+    match post {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let post = post.unwrap();
+
+  if post.poster_id != scoped_token.id && !scoped_token.is_admin() {
+    return StatusCode::FORBIDDEN.into_response();
+  }
+
+  let mut post_revisions = vec![];
+  for revision_id in [url_query.from, url_query.to] {
+    let post_revision = app_state
+      .post_revision_repository
+      .get_one_post_revision_by_id(revision_id)
+      .await;
+    if !post_revision.is_ok() {
+      match post_revision {
+        Err(DataAccessError::NotFound) => {
+          return StatusCode::NOT_FOUND.into_response();
+        }
+        _ => {
+          // @TODO-ZM: log error reason
+          return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+      }
+    }
+    let post_revision = post_revision.unwrap();
+
+    if post_revision.post_id != post.id {
+      return StatusCode::NOT_FOUND.into_response();
+    }
+
+    post_revisions.push(post_revision);
+  }
+
+  Json(json!({
+      "from": post_revisions[0],
+      "to": post_revisions[1],
+      "diffs": post_revisions[0].get_field_diffs(&post_revisions[1]),
+  }))
+  .into_response()
+}
+
+pub async fn restore_one_post_revision(
+  ConnectInfo(ip): ConnectInfo<SocketAddr>,
+  State(app_state): State<AppState>,
+  scoped_token: ScopedToken,
+  Path((post_id, revision_id)): Path<(u32, u32)>,
+) -> impl IntoResponse {
+  let post = app_state.post_repository.get_one_post_by_id(post_id).await;
+  if !post.is_ok() {
+    match post {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let post = post.unwrap();
+
+  if post.poster_id != scoped_token.id {
+    return StatusCode::FORBIDDEN.into_response();
+  }
+
+  // @TODO-ZM: write a macro for this
+  match app_state.security_service.rate_limit(vec![
+    RateLimitConstraint {
+      id: format!("restore_one_post_revision-1-{}", scoped_token.id),
+      max_requests: 1,
+      duration_ms: 2000,
+    },
+    RateLimitConstraint {
+      id: format!("restore_one_post_revision-2-{}", ip.ip()),
+      max_requests: 60,
+      duration_ms: 60_000,
+    },
+  ]) {
+    Ok(_) => {}
+    Err(SecurityError::InternalError) => {
+      // @TODO-ZM: log error reason
+      return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    Err(SecurityError::RateLimitError) => {
+      return StatusCode::TOO_MANY_REQUESTS.into_response();
+    }
+  }
+
+  let post_revision = app_state
+    .post_revision_repository
+    .get_one_post_revision_by_id(revision_id)
+    .await;
+  if !post_revision.is_ok() {
+    match post_revision {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let post_revision = post_revision.unwrap();
+
+  if post_revision.post_id != post.id {
+    return StatusCode::NOT_FOUND.into_response();
+  }
+
+  // tags might have been removed since the revision
+  let compact_tags = app_state
+    .tag_repository
+    .get_many_compact_tags_by_ids(&post_revision.tag_ids)
+    .await;
+  if !compact_tags.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let compact_tags = compact_tags.unwrap();
+
+  let poster = app_state
+    .account_repository
+    .get_one_account_by_id(post.poster_id)
+    .await;
+  if !poster.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let poster = poster.unwrap();
+
+  // the rules might have changed since the revision was written
+  let spam_score = app_state.spam_service.get_post_spam_score(
+    &post_revision.title,
+    &post_revision.description,
+    &poster.email,
+  );
+  if spam_score.verdict == SpamVerdict::Reject {
+    return (
+      StatusCode::UNPROCESSABLE_ENTITY,
+      Json(json!({
+          "spam_rules": spam_score.matched_rules,
+      })),
+    )
+      .into_response();
+  }
+
+  let duplicate = app_state
+    .post_repository
+    .get_one_post_duplicate(
+      Some(post.id),
+      Some(post.poster_id),
+      &post_revision.title,
+      &post_revision.description,
+    )
+    .await;
+  if !duplicate.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let similar_post_id = match duplicate.unwrap() {
+    Some(PostDuplicate::Exact { post_id }) => {
+      return (
+        StatusCode::CONFLICT,
+        Json(json!({
+            "duplicate_post_id": post_id,
+        })),
+      )
+        .into_response();
+    }
+    Some(PostDuplicate::Near { post_id }) => Some(post_id),
+    None => None,
+  };
+
+  // only the content and the attributes are restored, the post keeps its poster, publishing state and expiry
+  let was_live = post.is_live();
+  let description_html = render_markdown(&post_revision.description);
+  let restored_post = PartialPost {
+    id: None,
+    slug: Some(post_revision.slug),
+    title: Some(post_revision.title),
+    poster_id: None,
+    short_description: Some(post_revision.short_description),
+    description: Some(post_revision.description),
+    description_html: Some(description_html),
+    tag_ids: Some(compact_tags.iter().map(|tag| tag.id).collect::<Vec<u32>>()),
+    published_at: None,
+    is_published: None,
+    expires_at: None,
+    is_archived: None,
+    attributes: Some(post_revision.attributes),
+    moderation_status: get_edited_moderation_status(
+      poster.is_trusted,
+      &similar_post_id,
+      &spam_score.verdict,
+    ),
+    moderation_reason: None,
+    similar_post_id: Some(similar_post_id),
+  }
+  .to_post(post);
+
+  let update_result = app_state
+    .post_repository
+    .update_one_post(
+      &restored_post,
+      &spam_score,
+      &restored_post.to_db_post_revision(scoped_token.id, PostRevisionReason::Restored),
+    )
+    .await;
+  if !update_result.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+
+  // posts sent back to moderation are only unindexed
+  let task_names = match (was_live, restored_post.is_live()) {
+    (_, true) => vec![
+      TaskName::UndoIndexing {
+        model_name: "post".to_string(),
+        model_id: restored_post.id,
+      },
+      TaskName::Indexing {
+        model_name: "post".to_string(),
+        model_id: restored_post.id,
+      },
+    ],
+    (true, false) => vec![TaskName::UndoIndexing {
+      model_name: "post".to_string(),
+      model_id: restored_post.id,
+    }],
+    (false, false) => vec![],
+  };
+  for name in task_names {
+    let task_id = app_state
+      .task_repository
+      .create_one_task(DBTask {
+        name,
+        status: TaskStatus::Pending,
+        r#type: TaskType::Automated,
+      })
+      .await;
+    if !task_id.is_ok() {
+      // @TODO-ZM: log error reason
+      return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+  }
+
+  Json(json!({
+      "post": restored_post,
+      "tags": compact_tags,
+  }))
+  .into_response()
+}
+
+pub fn create_post_revision_router() -> Router<AppState> {
+  Router::new()
+    .route(
+      "/:post_id/revisions",
+      axum::routing::get(get_many_post_revisions),
+    )
+    .route(
+      "/:post_id/revisions/diff",
+      axum::routing::get(get_post_revision_diff),
+    )
+    .route(
+      "/:post_id/revisions/:revision_id/restore",
+      axum::routing::post(restore_one_post_revision),
+    )
+}
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum_macros::Display;
+use utility_types::omit;
+
+use crate::post::model::{DBPost, Post, PostAttributes, PostModerationStatus};
+
+// archiving, going live and being hidden by reports are recorded as revisions authored by no account
+pub const SYSTEM_AUTHOR_ID: u32 = 0;
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone)]
+#[serde(tag = "reason")] // to flatten the enum to the parent struct
+pub enum PostRevisionReason {
+  Created,
+  Published,
+  Updated,
+  Restored,
+  Deleted,
+  Archived,
+  Extended,
+  Moderated,
+  Reported,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[omit(DBPostRevision, [id, created_at], [Debug, Serialize, Deserialize, Clone])]
+pub struct PostRevision {
+  pub id: u32,
+  pub post_id: u32,
+  pub author_id: u32,
+  #[serde(flatten)]
+  pub reason: PostRevisionReason,
+  pub slug: String,
+  pub title: String,
+  pub poster_id: u32,
+  pub short_description: String,
+  pub description: String,
+  pub tag_ids: Vec<u32>,
+  pub published_at: String,
+  #[serde(default)]
+  pub expires_at: String,
+  #[serde(default)]
+  pub attributes: PostAttributes,
+  #[serde(default)]
+  pub moderation_status: PostModerationStatus,
+  #[serde(default)]
+  pub moderation_reason: String,
+  pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PostRevisionFieldDiff {
+  pub field: String,
+  pub from: Value,
+  pub to: Value,
+}
+
+// nested fields are dotted, each attribute is diffed on its own
+const DIFFABLE_FIELDS: [&str; 14] = [
+  "slug",
+  "title",
+  "poster_id",
+  "short_description",
+  "description",
+  "tag_ids",
+  "published_at",
+  "expires_at",
+  "attributes.wilaya_code",
+  "attributes.contract_type",
+  "attributes.remote_policy",
+  "attributes.salary",
+  "moderation_status",
+  "moderation_reason",
+];
+
+pub trait PostRevisionTrait {
+  fn get_field_diffs(&self, other: &PostRevision) -> Vec<PostRevisionFieldDiff>;
+}
+
+impl PostRevisionTrait for PostRevision {
+  fn get_field_diffs(&self, other: &PostRevision) -> Vec<PostRevisionFieldDiff> {
+    let from = serde_json::to_value(self).unwrap_or_default();
+    let to = serde_json::to_value(other).unwrap_or_default();
+
+    DIFFABLE_FIELDS
+      .iter()
+      .filter_map(|field| {
+        let pointer = format!("/{}", field.replace('.', "/"));
+        let from = from.pointer(&pointer).cloned().unwrap_or_default();
+        let to = to.pointer(&pointer).cloned().unwrap_or_default();
+
+        match from != to {
+          true => Some(PostRevisionFieldDiff {
+            field: field.to_string(),
+            from,
+            to,
+          }),
+          false => None,
+        }
+      })
+      .collect()
+  }
+}
+
+pub trait PostSnapshotTrait {
+  fn to_db_post_revision(&self, author_id: u32, reason: PostRevisionReason) -> DBPostRevision;
+}
+
+impl PostSnapshotTrait for Post {
+  fn to_db_post_revision(&self, author_id: u32, reason: PostRevisionReason) -> DBPostRevision {
+    DBPostRevision {
+      post_id: self.id,
+      author_id,
+      reason,
+      slug: self.slug.clone(),
+      title: self.title.clone(),
+      poster_id: self.poster_id,
+      short_description: self.short_description.clone(),
+      description: self.description.clone(),
+      tag_ids: self.tag_ids.clone(),
+      published_at: self.published_at.clone(),
+      expires_at: self.expires_at.clone(),
+      attributes: self.attributes.clone(),
+      moderation_status: self.moderation_status.clone(),
+      moderation_reason: self.moderation_reason.clone(),
+    }
+  }
+}
+
+// the post_id is only known once the post is created, see create_one_post
+impl PostSnapshotTrait for DBPost {
+  fn to_db_post_revision(&self, author_id: u32, reason: PostRevisionReason) -> DBPostRevision {
+    DBPostRevision {
+      post_id: 0,
+      author_id,
+      reason,
+      slug: self.slug.clone(),
+      title: self.title.clone(),
+      poster_id: self.poster_id,
+      short_description: self.short_description.clone(),
+      description: self.description.clone(),
+      tag_ids: self.tag_ids.clone(),
+      published_at: self.published_at.clone(),
+      expires_at: self.expires_at.clone(),
+      attributes: self.attributes.clone(),
+      moderation_status: self.moderation_status.clone(),
+      moderation_reason: self.moderation_reason.clone(),
+    }
+  }
+}
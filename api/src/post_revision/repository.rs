@@ -0,0 +1,221 @@
+use serde_json::json;
+use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite, SqliteConnection};
+use std::sync::Arc;
+
+use super::model::{DBPostRevision, PostRevision};
+use crate::{
+  _utils::error::DataAccessError,
+  post::repository::{get_post_attributes_from_row, get_salary_columns},
+};
+
+fn get_post_revision_from_row(row: &SqliteRow) -> Result<PostRevision, DataAccessError> {
+  let tag_ids = row.get::<String, _>("tag_ids");
+  let tag_ids = tag_ids
+    .split(",")
+    .filter(|id| !id.is_empty())
+    .map(|id| id.parse::<u32>())
+    .collect::<Vec<Result<u32, _>>>();
+  if tag_ids.iter().any(|id| id.is_err()) {
+    tracing::error!(
+      "Error while getting post revision, on parsing tag_ids, error: {:?}",
+      tag_ids
+    );
+    return Err(DataAccessError::InternalError);
+  }
+
+  let tag_ids = tag_ids
+    .iter()
+    .map(|id| id.clone().unwrap())
+    .collect::<Vec<u32>>();
+
+  let json_post_revision = json!({
+    "id": row.get::<u32, _>("id"),
+    "post_id": row.get::<u32, _>("post_id"),
+    "author_id": row.get::<u32, _>("author_id"),
+    "reason": row.get::<String, _>("reason"),
+    "slug": row.get::<String, _>("slug"),
+    "title": row.get::<String, _>("title"),
+    "poster_id": row.get::<u32, _>("poster_id"),
+    "short_description": row.get::<String, _>("short_description"),
+    "description": row.get::<String, _>("description"),
+    "tag_ids": tag_ids,
+    "published_at": row.get::<String, _>("published_at"),
+    "expires_at": row.get::<String, _>("expires_at"),
+    "attributes": get_post_attributes_from_row(row),
+    "moderation_status": row.get::<String, _>("moderation_status"),
+    "moderation_reason": row.get::<String, _>("moderation_reason"),
+    "created_at": row.get::<String, _>("created_at"),
+  });
+
+  let post_revision = serde_json::from_value::<PostRevision>(json_post_revision);
+  if post_revision.is_err() {
+    tracing::error!(
+      "Error while deserializing post revision: {:?}",
+      post_revision.err()
+    );
+    return Err(DataAccessError::InternalError);
+  }
+
+  Ok(post_revision.unwrap())
+}
+
+// shared with the repositories writing a revision along the change it records, in one transaction
+pub async fn insert_one_post_revision(
+  conn: &mut SqliteConnection,
+  post_revision: &DBPostRevision,
+) -> Result<u32, DataAccessError> {
+  let (salary_min, salary_max, salary_currency, salary_period) =
+    get_salary_columns(&post_revision.attributes);
+
+  let db_result = sqlx::query(
+    r#"
+    INSERT INTO post_revision (post_id, author_id, reason, slug, title, poster_id, short_description, description, tag_ids, published_at, expires_at,
+      wilaya_code, contract_type, remote_policy, salary_min, salary_max, salary_currency, salary_period, moderation_status,
+      moderation_reason, created_at)
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, strftime('%Y-%m-%dT%H:%M:%S.%fZ', 'now'))
+    "#,
+  )
+  .bind(post_revision.post_id)
+  .bind(post_revision.author_id)
+  .bind(post_revision.reason.to_string())
+  .bind(&post_revision.slug)
+  .bind(&post_revision.title)
+  .bind(post_revision.poster_id)
+  .bind(&post_revision.short_description)
+  .bind(&post_revision.description)
+  .bind(&post_revision.tag_ids.iter().map(|id| id.to_string()).collect::<Vec<String>>().join(","))
+  .bind(&post_revision.published_at)
+  .bind(&post_revision.expires_at)
+  .bind(post_revision.attributes.wilaya_code)
+  .bind(
+    post_revision
+      .attributes
+      .contract_type
+      .as_ref()
+      .map(|contract_type| contract_type.to_string()),
+  )
+  .bind(
+    post_revision
+      .attributes
+      .remote_policy
+      .as_ref()
+      .map(|remote_policy| remote_policy.to_string()),
+  )
+  .bind(salary_min)
+  .bind(salary_max)
+  .bind(salary_currency)
+  .bind(salary_period)
+  .bind(post_revision.moderation_status.to_string())
+  .bind(&post_revision.moderation_reason)
+  .execute(&mut *conn)
+  .await;
+
+  if db_result.is_err() {
+    tracing::error!("Error while creating one post revision: {:?}", db_result);
+    return Err(DataAccessError::InternalError);
+  }
+
+  let id = db_result.unwrap().last_insert_rowid() as u32;
+  Ok(id)
+}
+
+pub struct PostRevisionRepository {
+  main_sql_db: Arc<Pool<Sqlite>>,
+}
+
+impl PostRevisionRepository {
+  pub fn new(main_sql_db: Arc<Pool<Sqlite>>) -> Self {
+    Self { main_sql_db }
+  }
+
+  pub async fn create_one_post_revision(
+    &self,
+    post_revision: &DBPostRevision,
+  ) -> Result<u32, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    insert_one_post_revision(&mut conn, post_revision).await
+  }
+
+  pub async fn get_many_post_revisions_by_post_id(
+    &self,
+    post_id: u32,
+  ) -> Result<Vec<PostRevision>, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT *
+      FROM post_revision
+      WHERE post_id = $1
+      ORDER BY id DESC
+      "#,
+    )
+    .bind(post_id)
+    .fetch_all(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting many post revisions by post id: {:?}",
+        db_result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+    let db_result = db_result.unwrap();
+
+    let mut post_revisions = vec![];
+    for row in db_result {
+      post_revisions.push(get_post_revision_from_row(&row)?);
+    }
+
+    Ok(post_revisions)
+  }
+
+  pub async fn get_one_post_revision_by_id(
+    &self,
+    id: u32,
+  ) -> Result<PostRevision, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT *
+      FROM post_revision
+      WHERE id = $1
+      "#,
+    )
+    .bind(id)
+    .fetch_one(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      match db_result.err().unwrap() {
+        sqlx::Error::RowNotFound => {
+          return Err(DataAccessError::NotFound);
+        }
+        err => {
+          tracing::error!("Error while getting one post revision by id: {:?}", err);
+          return Err(DataAccessError::InternalError);
+        }
+      }
+    }
+
+    get_post_revision_from_row(&db_result.unwrap())
+  }
+}
@@ -0,0 +1,155 @@
+use axum::{
+  extract::{ConnectInfo, Path, Query, State},
+  response::IntoResponse,
+  Json, Router,
+};
+use hyper::StatusCode;
+use serde_json::json;
+use std::net::SocketAddr;
+
+use super::model::{PostEventKind, PostStat};
+use crate::{
+  _entry::state::AppState,
+  _utils::{error::DataAccessError, ip_hash::get_ip_hash, query::PostStatQuery},
+  auth::service::ScopedToken,
+  post::model::PostTrait,
+};
+
+const DEFAULT_STAT_DAYS: u32 = 30;
+const MAX_STAT_DAYS: u32 = 365;
+
+// counting is best effort, a failure is logged but never fails the request it's recorded from
+pub async fn record_one_post_event(
+  app_state: &AppState,
+  post_id: u32,
+  kind: PostEventKind,
+  ip: &SocketAddr,
+) {
+  let day = chrono::Utc::now().format("%Y-%m-%d").to_string();
+  // salted with the day so a visitor can't be followed from one day to the next
+  let ip_hash = get_ip_hash(
+    &ip.ip(),
+    &day,
+    &app_state.config_service.get_config().ip_hash_secret,
+  );
+
+  let post_event = app_state
+    .post_stat_repository
+    .create_one_post_event(post_id, &kind, &ip_hash, &day)
+    .await;
+  if post_event.is_err() {
+    tracing::error!("Error while recording {} event for post {}", kind, post_id);
+  }
+}
+
+pub async fn create_one_apply_click(
+  ConnectInfo(ip): ConnectInfo<SocketAddr>,
+  State(app_state): State<AppState>,
+  Path(post_id): Path<u32>,
+) -> impl IntoResponse {
+  let post = app_state.post_repository.get_one_post_by_id(post_id).await;
+  if !post.is_ok() {
+    match post {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let post = post.unwrap();
+
+  if !post.is_live() {
+    return StatusCode::NOT_FOUND.into_response();
+  }
+
+  record_one_post_event(&app_state, post.id, PostEventKind::ApplyClick, &ip).await;
+
+  StatusCode::NO_CONTENT.into_response()
+}
+
+// today's counts are only as fresh as the last aggregation
+pub async fn get_many_post_stats(
+  State(app_state): State<AppState>,
+  scoped_token: ScopedToken,
+  Path(post_id): Path<u32>,
+  Query(url_query): Query<PostStatQuery>,
+) -> impl IntoResponse {
+  let days = url_query.days.unwrap_or(DEFAULT_STAT_DAYS);
+  if days == 0 || days > MAX_STAT_DAYS {
+    return StatusCode::BAD_REQUEST.into_response();
+  }
+
+  let post = app_state.post_repository.get_one_post_by_id(post_id).await;
+  if !post.is_ok() {
+    match post {
+      Err(DataAccessError::NotFound) => {
+        return StatusCode::NOT_FOUND.into_response();
+      }
+      _ => {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+    }
+  }
+  let post = post.unwrap();
+
+  if post.poster_id != scoped_token.id {
+    return StatusCode::FORBIDDEN.into_response();
+  }
+
+  let today = chrono::Utc::now().date_naive();
+  let from_day = today - chrono::Duration::days(days as i64 - 1);
+
+  let post_stats = app_state
+    .post_stat_repository
+    .get_many_post_stats_by_post_id(
+      post_id,
+      &from_day.format("%Y-%m-%d").to_string(),
+      &today.format("%Y-%m-%d").to_string(),
+    )
+    .await;
+  if !post_stats.is_ok() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let post_stats = post_stats.unwrap();
+
+  // days without any event are filled with zeros, so the series has no gaps
+  let stats = from_day
+    .iter_days()
+    .take(days as usize)
+    .map(|day| {
+      let day = day.format("%Y-%m-%d").to_string();
+      post_stats
+        .iter()
+        .find(|post_stat| post_stat.day == day)
+        .cloned()
+        .unwrap_or(PostStat {
+          day,
+          view_count: 0,
+          apply_click_count: 0,
+        })
+    })
+    .collect::<Vec<PostStat>>();
+
+  Json(json!({
+      "stats": stats,
+      "total": {
+        "view_count": stats.iter().map(|stat| stat.view_count).sum::<u32>(),
+        "apply_click_count": stats.iter().map(|stat| stat.apply_click_count).sum::<u32>(),
+      },
+  }))
+  .into_response()
+}
+
+pub fn create_post_stat_router() -> Router<AppState> {
+  Router::new()
+    .route(
+      "/:post_id/apply_clicks",
+      axum::routing::post(create_one_apply_click),
+    )
+    .route("/:post_id/stats", axum::routing::get(get_many_post_stats))
+}
@@ -0,0 +1,62 @@
+use crate::{_entry::state::AppState, _utils::error::BootError};
+use std::{
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+  },
+  time::Duration,
+};
+use tokio_cron_scheduler::Job;
+
+pub struct PostStatCronJob {
+  pub app_state: AppState,
+}
+
+async fn run_aggregating_cron_job(app_state: AppState) {
+  tracing::info!("🚀 Aggregating post stats");
+
+  let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+
+  let aggregation_result = app_state
+    .post_stat_repository
+    .aggregate_post_events(&today)
+    .await;
+  if aggregation_result.is_err() {
+    tracing::error!("Error while aggregating post stats");
+    return;
+  }
+
+  tracing::info!("✅ Aggregated post stats");
+}
+
+impl PostStatCronJob {
+  // runs more than once a day so today's counts don't lag too far behind
+  pub fn create_aggregating_cron_job(&self) -> Result<Job, BootError> {
+    let app_state = self.app_state.clone();
+    let is_job_running = Arc::new(AtomicBool::new(false));
+
+    let job = Job::new_repeated_async(Duration::from_secs(60 * 60), move |_, __| {
+      let app_state = app_state.clone();
+      let is_job_running = is_job_running.clone();
+
+      return Box::pin(async move {
+        let compare_and_swap_result =
+          is_job_running.compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed);
+        if compare_and_swap_result.is_ok() && compare_and_swap_result.unwrap() == false {
+          run_aggregating_cron_job(app_state.clone()).await;
+          is_job_running.store(false, Ordering::Relaxed);
+        } else {
+          tracing::info!("⏳ Still aggregating post stats... ");
+        }
+      });
+    });
+
+    if job.is_err() {
+      tracing::error!("Error while creating post stat aggregating cron job");
+      return Err(BootError::CronJobSetupError);
+    }
+    let job = job.unwrap();
+
+    Ok(job)
+  }
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PostEventKind {
+  View,
+  ApplyClick,
+}
+
+// one day of a post's counts, each visitor counted at most once per day
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PostStat {
+  pub day: String,
+  pub view_count: u32,
+  pub apply_click_count: u32,
+}
@@ -0,0 +1,162 @@
+use sqlx::{Pool, Row, Sqlite};
+use std::sync::Arc;
+
+use super::model::{PostEventKind, PostStat};
+use crate::_utils::error::DataAccessError;
+
+pub struct PostStatRepository {
+  main_sql_db: Arc<Pool<Sqlite>>,
+}
+
+impl PostStatRepository {
+  pub fn new(main_sql_db: Arc<Pool<Sqlite>>) -> Self {
+    Self { main_sql_db }
+  }
+
+  // repeated events from the same ip hash on the same day are ignored
+  pub async fn create_one_post_event(
+    &self,
+    post_id: u32,
+    kind: &PostEventKind,
+    ip_hash: &String,
+    day: &String,
+  ) -> Result<(), DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      INSERT OR IGNORE INTO post_event (post_id, kind, ip_hash, day, created_at)
+      VALUES ($1, $2, $3, $4, strftime('%Y-%m-%dT%H:%M:%S.%fZ', 'now'))
+      "#,
+    )
+    .bind(post_id)
+    .bind(kind.to_string())
+    .bind(ip_hash)
+    .bind(day)
+    .execute(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while creating one post event: {:?}", db_result);
+      return Err(DataAccessError::InternalError);
+    }
+
+    Ok(())
+  }
+
+  // counts every day that still has events, then drops the events of the days before `today`,
+  // today's are kept to keep de-duplicating against them
+  pub async fn aggregate_post_events(&self, today: &String) -> Result<(), DataAccessError> {
+    let tx = self.main_sql_db.begin().await;
+    if tx.is_err() {
+      tracing::error!("Error while starting sql transaction: {:?}", tx);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut tx = tx.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      INSERT INTO post_stat (post_id, day, view_count, apply_click_count)
+      SELECT post_id, day, SUM(kind = $1), SUM(kind = $2)
+      FROM post_event
+      WHERE true
+      GROUP BY post_id, day
+      ON CONFLICT (post_id, day) DO UPDATE SET
+        view_count = excluded.view_count,
+        apply_click_count = excluded.apply_click_count
+      "#,
+    )
+    .bind(PostEventKind::View.to_string())
+    .bind(PostEventKind::ApplyClick.to_string())
+    .execute(&mut *tx)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while aggregating post events: {:?}", db_result);
+      return Err(DataAccessError::InternalError);
+    }
+
+    let db_result = sqlx::query(
+      r#"
+      DELETE FROM post_event
+      WHERE day < $1
+      "#,
+    )
+    .bind(today)
+    .execute(&mut *tx)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while deleting aggregated post events: {:?}",
+        db_result
+      );
+      return Err(DataAccessError::InternalError);
+    }
+
+    let commit_result = tx.commit().await;
+    if commit_result.is_err() {
+      tracing::error!(
+        "Error while committing post events aggregation: {:?}",
+        commit_result
+      );
+      return Err(DataAccessError::InternalError);
+    }
+
+    Ok(())
+  }
+
+  // days without any event are missing, oldest first
+  pub async fn get_many_post_stats_by_post_id(
+    &self,
+    post_id: u32,
+    from_day: &String,
+    to_day: &String,
+  ) -> Result<Vec<PostStat>, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT day, view_count, apply_click_count
+      FROM post_stat
+      WHERE post_id = $1 AND day >= $2 AND day <= $3
+      ORDER BY day ASC
+      "#,
+    )
+    .bind(post_id)
+    .bind(from_day)
+    .bind(to_day)
+    .fetch_all(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting many post stats by post id: {:?}",
+        db_result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+    let db_result = db_result.unwrap();
+
+    let post_stats = db_result
+      .iter()
+      .map(|row| PostStat {
+        day: row.get::<String, _>("day"),
+        view_count: row.get::<u32, _>("view_count"),
+        apply_click_count: row.get::<u32, _>("apply_click_count"),
+      })
+      .collect::<Vec<PostStat>>();
+
+    Ok(post_stats)
+  }
+}
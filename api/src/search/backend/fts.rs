@@ -0,0 +1,488 @@
+use axum::async_trait;
+use sqlx::{Pool, QueryBuilder, Row, Sqlite};
+use std::{collections::HashSet, sync::Arc};
+
+use super::SearchBackend;
+use crate::{
+  _utils::{error::SearchError, string::get_searchable_words},
+  account::model::{AccountNameTrait, CompactAccount},
+  post::model::Post,
+  search::{model::ParsedSearchQuery, stemmer::Stemmer},
+  tag::model::CompactTag,
+};
+
+// same order as the columns of post_fts: title, poster_display_name, short_description, tag_names, description
+const POST_FTS_BM25_WEIGHTS: &str = "100.0, 50.0, 25.0, 5.0, 1.0";
+// same order as the columns of account_fts: display_name, slug
+const ACCOUNT_FTS_BM25_WEIGHTS: &str = "100.0, 50.0";
+const PREFIX_QUERY_MIN_LENGTH: usize = 3;
+
+// stores the normalized words and their stems rather than the raw text, so the FTS5 tokenizer sees the same words as queries
+struct PostDocument {
+  id: u32,
+  title: String,
+  poster_display_name: String,
+  short_description: String,
+  tag_names: String,
+  description: String,
+}
+
+fn quote_fts_string(s: &str) -> String {
+  format!("\"{}\"", s.replace("\"", "\"\""))
+}
+
+// stems go after all the words, so phrases still match consecutive words
+fn get_searchable_text(text: &String, stemmer: &Stemmer) -> String {
+  let words = get_searchable_words(text);
+
+  let mut stems: Vec<String> = vec![];
+  for word in &words {
+    let stem = stemmer.stem(word);
+    if !words.contains(&stem) && !stems.contains(&stem) {
+      stems.push(stem);
+    }
+  }
+
+  words
+    .into_iter()
+    .chain(stems.into_iter())
+    .collect::<Vec<String>>()
+    .join(" ")
+}
+
+// builds an FTS5 MATCH expression where every user input is quoted, so it can't inject FTS5 syntax
+fn get_fts_match_expression(search_query: &ParsedSearchQuery) -> String {
+  search_query
+    .words
+    .iter()
+    .map(
+      |word| match word.chars().count() >= PREFIX_QUERY_MIN_LENGTH {
+        true => format!("{}*", quote_fts_string(word)),
+        false => quote_fts_string(word),
+      },
+    )
+    .chain(
+      search_query
+        .phrases
+        .iter()
+        .map(|phrase| quote_fts_string(&phrase.join(" "))),
+    )
+    // documents containing the exact words match both the words and their stems, hence score higher
+    .chain(search_query.stems.iter().map(|stem| quote_fts_string(stem)))
+    .collect::<Vec<String>>()
+    .join(" OR ")
+}
+
+fn push_scored_fts_query(
+  query_builder: &mut QueryBuilder<'_, Sqlite>,
+  fts_table: &str,
+  bm25_weights: &str,
+  search_queries: &Vec<ParsedSearchQuery>,
+) {
+  let search_queries_count = search_queries.len();
+
+  query_builder.push(
+    r#"
+    SELECT model_id, CAST(SUM(score) AS REAL) AS score
+    FROM (
+    "#,
+  );
+
+  for (index, search_query) in search_queries.iter().enumerate() {
+    if index > 0 {
+      query_builder.push(" UNION ALL ");
+    }
+
+    // bm25 is negative, the more relevant the smaller,
+    // it's read from the rank column as bm25() can't be called once the query is joined with indexed_post
+    query_builder.push(
+      r#"
+      SELECT rowid AS model_id, -rank * "#,
+    );
+    query_builder.push_bind((search_queries_count - index) as i64);
+    query_builder.push(format!(
+      r#" AS score
+      FROM {}
+      WHERE {} MATCH "#,
+      fts_table, fts_table
+    ));
+    query_builder.push_bind(get_fts_match_expression(search_query));
+    query_builder.push(format!(" AND rank MATCH 'bm25({})'", bm25_weights));
+  }
+
+  query_builder.push(
+    r#"
+    )
+    GROUP BY model_id
+    "#,
+  );
+}
+
+pub struct FTSSearchBackend {
+  search_sql_db: Arc<Pool<Sqlite>>,
+  stemmer: Stemmer,
+}
+
+impl FTSSearchBackend {
+  pub fn new(search_sql_db: Arc<Pool<Sqlite>>, stemmer: Stemmer) -> Self {
+    Self {
+      search_sql_db,
+      stemmer,
+    }
+  }
+}
+
+#[async_trait]
+impl SearchBackend for FTSSearchBackend {
+  async fn index_posts(
+    &self,
+    posts: Vec<Post>,
+    tags: Vec<CompactTag>,
+    posters: Vec<CompactAccount>,
+  ) -> Result<(), SearchError> {
+    let mut post_documents: Vec<PostDocument> = vec![];
+    for post in posts {
+      let mut tag_names = vec![];
+      for tag_id in &post.tag_ids {
+        let tag = tags.iter().find(|tag| tag.id == *tag_id);
+        if tag.is_none() {
+          tracing::error!("Failed to find the tag by id {}", tag_id,);
+          return Err(SearchError::InternalError);
+        };
+        tag_names.push(tag.unwrap().name.clone());
+      }
+
+      let poster = posters.iter().find(|poster| poster.id == post.poster_id);
+      if poster.is_none() {
+        tracing::error!("Failed to find the poster");
+        return Err(SearchError::InternalError);
+      }
+      let poster = poster.unwrap();
+
+      post_documents.push(PostDocument {
+        id: post.id,
+        title: get_searchable_text(&post.title, &self.stemmer),
+        poster_display_name: get_searchable_text(&poster.get_display_name(), &self.stemmer),
+        short_description: get_searchable_text(&post.short_description, &self.stemmer),
+        tag_names: get_searchable_text(&tag_names.join(" "), &self.stemmer),
+        description: get_searchable_text(&post.description, &self.stemmer),
+      });
+    }
+
+    if post_documents.is_empty() {
+      return Ok(());
+    }
+
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection to index: {:?}", conn);
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    // remove previous versions of the same posts, as FTS5 tables have no unique constraint to upsert on
+    let mut query_builder = QueryBuilder::new("DELETE FROM post_fts WHERE rowid IN (");
+    let mut separated = query_builder.separated(", ");
+    for post_document in &post_documents {
+      separated.push_bind(post_document.id);
+    }
+    separated.push_unseparated(")");
+
+    let db_result = query_builder.build().execute(&mut *conn).await;
+    if db_result.is_err() {
+      tracing::error!("Error while removing stale posts: {:?}", db_result);
+      return Err(SearchError::InternalError);
+    }
+
+    let mut query_builder = QueryBuilder::new(
+      "INSERT INTO post_fts (rowid, title, poster_display_name, short_description, tag_names, description) ",
+    );
+
+    query_builder.push_values(post_documents, |mut b, post_document| {
+      b.push_bind(post_document.id)
+        .push_bind(post_document.title)
+        .push_bind(post_document.poster_display_name)
+        .push_bind(post_document.short_description)
+        .push_bind(post_document.tag_names)
+        .push_bind(post_document.description);
+    });
+
+    let db_result = query_builder.build().execute(&mut *conn).await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while indexing posts: {:?}", db_result);
+      return Err(SearchError::InternalError);
+    }
+
+    Ok(())
+  }
+
+  async fn unindex_posts(&self, post_ids: Vec<u32>) -> Result<(), SearchError> {
+    if post_ids.is_empty() {
+      return Ok(());
+    }
+
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection to unindex: {:?}", conn);
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let mut query_builder = QueryBuilder::new("DELETE FROM post_fts WHERE rowid IN (");
+
+    let mut separated = query_builder.separated(", ");
+    for post_id in post_ids {
+      separated.push_bind(post_id);
+    }
+    separated.push_unseparated(")");
+
+    let db_result = query_builder.build().execute(&mut *conn).await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while unindexing posts: {:?}", db_result);
+      return Err(SearchError::InternalError);
+    }
+
+    Ok(())
+  }
+
+  async fn index_accounts(&self, accounts: Vec<CompactAccount>) -> Result<(), SearchError> {
+    if accounts.is_empty() {
+      return Ok(());
+    }
+
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection to index: {:?}", conn);
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let mut query_builder = QueryBuilder::new("DELETE FROM account_fts WHERE rowid IN (");
+    let mut separated = query_builder.separated(", ");
+    for account in &accounts {
+      separated.push_bind(account.id);
+    }
+    separated.push_unseparated(")");
+
+    let db_result = query_builder.build().execute(&mut *conn).await;
+    if db_result.is_err() {
+      tracing::error!("Error while removing stale accounts: {:?}", db_result);
+      return Err(SearchError::InternalError);
+    }
+
+    let mut query_builder =
+      QueryBuilder::new("INSERT INTO account_fts (rowid, display_name, slug) ");
+
+    query_builder.push_values(accounts, |mut b, account| {
+      b.push_bind(account.id)
+        .push_bind(get_searchable_text(
+          &account.get_display_name(),
+          &self.stemmer,
+        ))
+        .push_bind(get_searchable_text(&account.slug, &self.stemmer));
+    });
+
+    let db_result = query_builder.build().execute(&mut *conn).await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while indexing accounts: {:?}", db_result);
+      return Err(SearchError::InternalError);
+    }
+
+    Ok(())
+  }
+
+  async fn unindex_accounts(&self, account_ids: Vec<u32>) -> Result<(), SearchError> {
+    if account_ids.is_empty() {
+      return Ok(());
+    }
+
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection to unindex: {:?}", conn);
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let mut query_builder = QueryBuilder::new("DELETE FROM account_fts WHERE rowid IN (");
+
+    let mut separated = query_builder.separated(", ");
+    for account_id in account_ids {
+      separated.push_bind(account_id);
+    }
+    separated.push_unseparated(")");
+
+    let db_result = query_builder.build().execute(&mut *conn).await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while unindexing accounts: {:?}", db_result);
+      return Err(SearchError::InternalError);
+    }
+
+    Ok(())
+  }
+
+  fn push_scored_posts_query(
+    &self,
+    query_builder: &mut QueryBuilder<'_, Sqlite>,
+    search_queries: &Vec<ParsedSearchQuery>,
+  ) {
+    push_scored_fts_query(
+      query_builder,
+      "post_fts",
+      POST_FTS_BM25_WEIGHTS,
+      search_queries,
+    );
+  }
+
+  fn push_scored_accounts_query(
+    &self,
+    query_builder: &mut QueryBuilder<'_, Sqlite>,
+    search_queries: &Vec<ParsedSearchQuery>,
+  ) {
+    push_scored_fts_query(
+      query_builder,
+      "account_fts",
+      ACCOUNT_FTS_BM25_WEIGHTS,
+      search_queries,
+    );
+  }
+
+  async fn get_many_correction_words(&self) -> Result<Vec<(String, u32)>, SearchError> {
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!(
+        "Error while getting sql connection to get correction words: {:?}",
+        conn
+      );
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT word, SUM(frequency) AS frequency
+      FROM (
+        SELECT term AS word, doc AS frequency
+        FROM post_fts_vocab
+        WHERE col IN ('title', 'poster_display_name', 'short_description', 'tag_names')
+        UNION ALL
+        SELECT term AS word, doc AS frequency
+        FROM account_fts_vocab
+        WHERE col = 'display_name'
+      )
+      GROUP BY word
+      "#,
+    )
+    .fetch_all(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while getting all words: {:?}", db_result.err());
+      return Err(SearchError::InternalError);
+    }
+    let db_result = db_result.unwrap();
+
+    let mut words = vec![];
+
+    for row in db_result {
+      words.push((
+        row.get::<String, _>("word"),
+        row.get::<i64, _>("frequency") as u32,
+      ));
+    }
+
+    Ok(words)
+  }
+
+  async fn get_many_title_word_frequencies(
+    &self,
+    limit: u32,
+  ) -> Result<Vec<(String, u32)>, SearchError> {
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!(
+        "Error while getting sql connection to get title words: {:?}",
+        conn
+      );
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT term AS word, doc AS frequency
+      FROM post_fts_vocab
+      WHERE col = 'title'
+      ORDER BY frequency DESC
+      LIMIT $1
+      "#,
+    )
+    .bind(limit)
+    .fetch_all(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while getting title words: {:?}", db_result.err());
+      return Err(SearchError::InternalError);
+    }
+    let db_result = db_result.unwrap();
+
+    let word_frequencies = db_result
+      .iter()
+      .map(|row| {
+        (
+          row.get::<String, _>("word"),
+          row.get::<i64, _>("frequency") as u32,
+        )
+      })
+      .collect::<Vec<(String, u32)>>();
+
+    // the title column also holds the stems appended by get_searchable_text, eg: "manag" next to "managers",
+    // a term that is the stem of another term is most likely one of them
+    let stems_of_other_words = word_frequencies
+      .iter()
+      .map(|(word, _)| (word, self.stemmer.stem(word)))
+      .filter(|(word, stem)| *word != stem)
+      .map(|(_, stem)| stem)
+      .collect::<HashSet<String>>();
+
+    let word_frequencies = word_frequencies
+      .into_iter()
+      .filter(|(word, _)| !stems_of_other_words.contains(word))
+      .collect();
+
+    Ok(word_frequencies)
+  }
+
+  async fn get_indexed_post_count(&self) -> Result<u32, SearchError> {
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection to count: {:?}", conn);
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT COUNT(*) AS count
+      FROM post_fts
+      "#,
+    )
+    .fetch_one(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting indexed post count: {:?}",
+        db_result.err()
+      );
+      return Err(SearchError::InternalError);
+    }
+    let count = db_result.unwrap().get::<i64, _>("count") as u32;
+
+    Ok(count)
+  }
+}
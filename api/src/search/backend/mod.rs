@@ -0,0 +1,57 @@
+pub mod fts;
+pub mod word;
+
+use axum::async_trait;
+use sqlx::{QueryBuilder, Sqlite};
+
+use super::model::ParsedSearchQuery;
+use crate::{
+  _utils::error::SearchError, account::model::CompactAccount, post::model::Post,
+  tag::model::CompactTag,
+};
+
+#[async_trait]
+pub trait SearchBackend: Send + Sync {
+  // re-indexing a post replaces its previous version
+  async fn index_posts(
+    &self,
+    posts: Vec<Post>,
+    tags: Vec<CompactTag>,
+    posters: Vec<CompactAccount>,
+  ) -> Result<(), SearchError>;
+
+  async fn unindex_posts(&self, post_ids: Vec<u32>) -> Result<(), SearchError>;
+
+  // re-indexing an account replaces its previous version
+  async fn index_accounts(&self, accounts: Vec<CompactAccount>) -> Result<(), SearchError>;
+
+  async fn unindex_accounts(&self, account_ids: Vec<u32>) -> Result<(), SearchError>;
+
+  // pushes a query selecting the "model_id" and the REAL "score" of every matching post,
+  // search_queries are sorted by priority, the first one being the original query
+  fn push_scored_posts_query(
+    &self,
+    query_builder: &mut QueryBuilder<'_, Sqlite>,
+    search_queries: &Vec<ParsedSearchQuery>,
+  );
+
+  // same as push_scored_posts_query, for accounts
+  fn push_scored_accounts_query(
+    &self,
+    query_builder: &mut QueryBuilder<'_, Sqlite>,
+    search_queries: &Vec<ParsedSearchQuery>,
+  );
+
+  // words eligible to be suggested as spelling corrections, with the number of posts and accounts they appear in,
+  // they're returned as indexed rather than stemmed: the service stems them with the current stemmer
+  // and keeps one of the words sharing a stem to show as the correction
+  async fn get_many_correction_words(&self) -> Result<Vec<(String, u32)>, SearchError>;
+
+  // words appearing in post titles, with the number of posts they appear in, most frequent first
+  async fn get_many_title_word_frequencies(
+    &self,
+    limit: u32,
+  ) -> Result<Vec<(String, u32)>, SearchError>;
+
+  async fn get_indexed_post_count(&self) -> Result<u32, SearchError>;
+}
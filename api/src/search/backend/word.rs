@@ -0,0 +1,445 @@
+use axum::async_trait;
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, QueryBuilder, Row, Sqlite};
+use std::sync::Arc;
+
+use super::SearchBackend;
+use crate::{
+  _utils::{error::SearchError, string::get_searchable_words},
+  account::model::{AccountNameTrait, CompactAccount},
+  post::model::Post,
+  search::{
+    model::{ParsedSearchQuery, ParsedSearchQueryTrait},
+    stemmer::Stemmer,
+  },
+  tag::model::CompactTag,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WordIndex {
+  word: String,
+  stem: String,
+  model_id: u32,
+  appear_in: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct WordRecord {
+  word: String,
+}
+
+fn push_scored_models_query(
+  query_builder: &mut QueryBuilder<'_, Sqlite>,
+  model_type: &'static str,
+  search_queries: &Vec<ParsedSearchQuery>,
+) {
+  let search_queries_count = search_queries.len();
+
+  query_builder.push(
+    r#"
+    SELECT model_id, CAST(SUM(score) AS REAL) AS score
+    FROM (
+    "#,
+  );
+
+  for (index, search_query) in search_queries.iter().enumerate() {
+    if index > 0 {
+      query_builder.push(" UNION ALL ");
+    }
+
+    query_builder.push(
+      r#"
+      SELECT model_id, COUNT(*) *
+      CASE
+        WHEN appear_in = 'post_title' THEN 100
+        WHEN appear_in = 'post_poster_display_name' THEN 50
+        WHEN appear_in = 'post_short_description' THEN 25
+        WHEN appear_in = 'post_tag_name' THEN 5
+        WHEN appear_in = 'account_display_name' THEN 100
+        WHEN appear_in = 'account_slug' THEN 50
+        ELSE 1
+      END *
+      CASE WHEN word IN ("#,
+    );
+
+    // exact words score twice as much as words only sharing their stem
+    let mut separated = query_builder.separated(", ");
+    for word in search_query.get_all_words() {
+      separated.push_bind(word);
+    }
+    separated.push_unseparated(") THEN 2 ELSE 1 END * ");
+
+    query_builder.push_bind((search_queries_count - index) as i64);
+    query_builder.push(
+      r#" AS score
+      FROM word
+      WHERE model_type = "#,
+    );
+    query_builder.push_bind(model_type);
+    query_builder.push(" AND stem IN (");
+
+    let mut separated = query_builder.separated(", ");
+    for stem in &search_query.stems {
+      separated.push_bind(stem.clone());
+    }
+    separated.push_unseparated(")");
+
+    query_builder.push(" GROUP BY word, model_id, appear_in");
+  }
+
+  query_builder.push(
+    r#"
+    )
+    GROUP BY model_id
+    "#,
+  );
+}
+
+pub struct WordSearchBackend {
+  search_sql_db: Arc<Pool<Sqlite>>,
+  stemmer: Stemmer,
+}
+
+impl WordSearchBackend {
+  pub fn new(search_sql_db: Arc<Pool<Sqlite>>, stemmer: Stemmer) -> Self {
+    Self {
+      search_sql_db,
+      stemmer,
+    }
+  }
+
+  async fn insert_word_indexes(
+    &self,
+    model_type: &'static str,
+    word_indexes: Vec<WordIndex>,
+  ) -> Result<(), SearchError> {
+    if word_indexes.is_empty() {
+      return Ok(());
+    }
+
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection to index: {:?}", conn);
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let mut query_builder =
+      QueryBuilder::new("INSERT INTO word (word, stem, model_type, model_id, appear_in) ");
+
+    query_builder.push_values(word_indexes, |mut b, new_word_index| {
+      b.push_bind(new_word_index.word)
+        .push_bind(new_word_index.stem)
+        .push_bind(model_type)
+        .push_bind(new_word_index.model_id)
+        .push_bind(new_word_index.appear_in);
+    });
+
+    let db_result = query_builder.build().execute(&mut *conn).await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while indexing {}s: {:?}", model_type, db_result);
+      return Err(SearchError::InternalError);
+    }
+
+    Ok(())
+  }
+
+  async fn delete_word_indexes(
+    &self,
+    model_type: &'static str,
+    model_ids: Vec<u32>,
+  ) -> Result<(), SearchError> {
+    if model_ids.is_empty() {
+      return Ok(());
+    }
+
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection to unindex: {:?}", conn);
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let mut query_builder = QueryBuilder::new("DELETE FROM word WHERE model_type = ");
+    query_builder.push_bind(model_type);
+    query_builder.push(" AND model_id IN (");
+
+    let mut separated = query_builder.separated(", ");
+    for model_id in model_ids {
+      separated.push_bind(model_id);
+    }
+    separated.push_unseparated(")");
+
+    let db_result = query_builder.build().execute(&mut *conn).await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while unindexing {}s: {:?}", model_type, db_result);
+      return Err(SearchError::InternalError);
+    }
+
+    Ok(())
+  }
+}
+
+#[async_trait]
+impl SearchBackend for WordSearchBackend {
+  async fn index_posts(
+    &self,
+    posts: Vec<Post>,
+    tags: Vec<CompactTag>,
+    posters: Vec<CompactAccount>,
+  ) -> Result<(), SearchError> {
+    self
+      .delete_word_indexes("post", posts.iter().map(|post| post.id).collect())
+      .await?;
+
+    let mut word_indexes: Vec<WordIndex> = vec![];
+    for post in posts {
+      get_searchable_words(&post.title)
+        .into_iter()
+        .for_each(|word| {
+          word_indexes.push(WordIndex {
+            stem: self.stemmer.stem(&word),
+            word,
+            model_id: post.id,
+            appear_in: "post_title".to_string(),
+          });
+        });
+
+      get_searchable_words(&post.description)
+        .into_iter()
+        .for_each(|word| {
+          word_indexes.push(WordIndex {
+            stem: self.stemmer.stem(&word),
+            word,
+            model_id: post.id,
+            appear_in: "post_description".to_string(),
+          });
+        });
+
+      for tag_id in post.tag_ids {
+        let tag = tags
+          .iter()
+          .find(|tag| tag.id == tag_id)
+          .map(|tag| tag.clone());
+
+        if tag.is_none() {
+          tracing::error!("Failed to find the tag by id {}", tag_id,);
+          return Err(SearchError::InternalError);
+        };
+        let tag = tag.unwrap();
+
+        get_searchable_words(&tag.name)
+          .into_iter()
+          .for_each(|word| {
+            word_indexes.push(WordIndex {
+              stem: self.stemmer.stem(&word),
+              word,
+              model_id: post.id,
+              appear_in: "post_tag_name".to_string(),
+            });
+          });
+      }
+
+      let poster = posters.iter().find(|poster| poster.id == post.poster_id);
+      if poster.is_none() {
+        tracing::error!("Failed to find the poster");
+        return Err(SearchError::InternalError);
+      }
+      let poster = poster.unwrap();
+
+      get_searchable_words(&poster.get_display_name())
+        .into_iter()
+        .for_each(|word| {
+          word_indexes.push(WordIndex {
+            stem: self.stemmer.stem(&word),
+            word,
+            model_id: post.id,
+            appear_in: "post_poster_display_name".to_string(),
+          });
+        });
+    }
+
+    self.insert_word_indexes("post", word_indexes).await
+  }
+
+  async fn unindex_posts(&self, post_ids: Vec<u32>) -> Result<(), SearchError> {
+    self.delete_word_indexes("post", post_ids).await
+  }
+
+  async fn index_accounts(&self, accounts: Vec<CompactAccount>) -> Result<(), SearchError> {
+    self
+      .delete_word_indexes(
+        "account",
+        accounts.iter().map(|account| account.id).collect(),
+      )
+      .await?;
+
+    let mut word_indexes: Vec<WordIndex> = vec![];
+    for account in accounts {
+      get_searchable_words(&account.get_display_name())
+        .into_iter()
+        .for_each(|word| {
+          word_indexes.push(WordIndex {
+            stem: self.stemmer.stem(&word),
+            word,
+            model_id: account.id,
+            appear_in: "account_display_name".to_string(),
+          });
+        });
+
+      get_searchable_words(&account.slug)
+        .into_iter()
+        .for_each(|word| {
+          word_indexes.push(WordIndex {
+            stem: self.stemmer.stem(&word),
+            word,
+            model_id: account.id,
+            appear_in: "account_slug".to_string(),
+          });
+        });
+    }
+
+    self.insert_word_indexes("account", word_indexes).await
+  }
+
+  async fn unindex_accounts(&self, account_ids: Vec<u32>) -> Result<(), SearchError> {
+    self.delete_word_indexes("account", account_ids).await
+  }
+
+  fn push_scored_posts_query(
+    &self,
+    query_builder: &mut QueryBuilder<'_, Sqlite>,
+    search_queries: &Vec<ParsedSearchQuery>,
+  ) {
+    push_scored_models_query(query_builder, "post", search_queries);
+  }
+
+  fn push_scored_accounts_query(
+    &self,
+    query_builder: &mut QueryBuilder<'_, Sqlite>,
+    search_queries: &Vec<ParsedSearchQuery>,
+  ) {
+    push_scored_models_query(query_builder, "account", search_queries);
+  }
+
+  async fn get_many_correction_words(&self) -> Result<Vec<(String, u32)>, SearchError> {
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!(
+        "Error while getting sql connection to get correction words: {:?}",
+        conn
+      );
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    // the stem column isn't read, as stems can't be shown to users, see SearchBackend
+    // @TODO-ZM: figure out how query $ replacement work, there is some unneeded "magic" here
+    let db_result = sqlx::query(
+      r#"
+        SELECT word, COUNT(DISTINCT model_id) AS frequency
+        FROM word
+        WHERE appear_in IN ('post_title', 'post_short_description', 'post_tag_name', 'post_poster_display_name', 'account_display_name')
+        GROUP BY word;
+      "#,
+    )
+    .fetch_all(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while getting all words: {:?}", db_result.err());
+      return Err(SearchError::InternalError);
+    }
+    let db_result = db_result.unwrap();
+
+    let mut words = vec![];
+
+    for row in db_result {
+      words.push((
+        row.get::<String, _>("word"),
+        row.get::<i64, _>("frequency") as u32,
+      ));
+    }
+
+    Ok(words)
+  }
+
+  async fn get_many_title_word_frequencies(
+    &self,
+    limit: u32,
+  ) -> Result<Vec<(String, u32)>, SearchError> {
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!(
+        "Error while getting sql connection to get title words: {:?}",
+        conn
+      );
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT word, COUNT(DISTINCT model_id) AS frequency
+      FROM word
+      WHERE model_type = 'post' AND appear_in = 'post_title'
+      GROUP BY word
+      ORDER BY frequency DESC
+      LIMIT $1
+      "#,
+    )
+    .bind(limit)
+    .fetch_all(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while getting title words: {:?}", db_result.err());
+      return Err(SearchError::InternalError);
+    }
+    let db_result = db_result.unwrap();
+
+    let word_frequencies = db_result
+      .iter()
+      .map(|row| {
+        (
+          row.get::<String, _>("word"),
+          row.get::<i64, _>("frequency") as u32,
+        )
+      })
+      .collect::<Vec<(String, u32)>>();
+
+    Ok(word_frequencies)
+  }
+
+  async fn get_indexed_post_count(&self) -> Result<u32, SearchError> {
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection to count: {:?}", conn);
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT COUNT(DISTINCT model_id) AS count
+      FROM word
+      WHERE model_type = 'post'
+      "#,
+    )
+    .fetch_one(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting indexed post count: {:?}",
+        db_result.err()
+      );
+      return Err(SearchError::InternalError);
+    }
+    let count = db_result.unwrap().get::<i64, _>("count") as u32;
+
+    Ok(count)
+  }
+}
@@ -6,23 +6,144 @@ use axum::{
 use hyper::StatusCode;
 use serde_json::json;
 
+use super::model::{
+  parse_search_cursor, SearchFilters, SearchHighlight, SearchModelType, SearchRecordTrait,
+};
 use crate::{
   _entry::state::AppState,
-  _utils::{query::SearchQuery, vec::sort_and_dedup_vec},
+  _utils::{
+    date::{format_date, parse_date},
+    query::{
+      split_comma_separated_values, FacetQuery, PaginationQueryTrait, PostAttributeQuery,
+      PostAttributeQueryTrait, SearchPaginationQuery, SearchQuery, SuggestQuery, TextSearchQuery,
+    },
+    vec::sort_and_dedup_vec,
+  },
+  post::model::{get_facet_tag_ids, PostFacets},
 };
 
+const MAX_SUGGEST_PREFIX_LENGTH: usize = 100;
+const OPEN_POSITION_COUNT_PER_ACCOUNT: u32 = 3;
+
 pub async fn search_posts(
   State(app_state): State<AppState>,
   url_query: Query<SearchQuery>,
+  attribute_query: Query<PostAttributeQuery>,
+  facet_query: Query<FacetQuery>,
+  pagination_query: Query<SearchPaginationQuery>,
 ) -> impl IntoResponse {
-  let post_ids = app_state
+  let attribute_filters = attribute_query.to_filters();
+  if attribute_filters.is_none() {
+    return StatusCode::BAD_REQUEST.into_response();
+  }
+  let attribute_filters = attribute_filters.unwrap();
+
+  let cursor = match &url_query.cursor {
+    Some(cursor) => {
+      let cursor = parse_search_cursor(cursor);
+      if cursor.is_none() {
+        return StatusCode::BAD_REQUEST.into_response();
+      }
+      cursor
+    }
+    None => None,
+  };
+
+  let published_after = match &url_query.published_after {
+    Some(published_after) => {
+      let published_after = parse_date(published_after).map(|date| format_date(&date));
+      if published_after.is_none() {
+        return StatusCode::BAD_REQUEST.into_response();
+      }
+      published_after
+    }
+    None => None,
+  };
+
+  // a repeated tag would otherwise be counted as an unknown one below
+  let mut tag_slugs = split_comma_separated_values(&url_query.tags);
+  sort_and_dedup_vec(&mut tag_slugs);
+
+  let filter_tags = app_state
+    .tag_repository
+    .get_many_compact_tags_by_slugs(&tag_slugs)
+    .await;
+  if filter_tags.is_err() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let filter_tags = filter_tags.unwrap();
+
+  // an unknown tag can't match any post
+  if filter_tags.len() < tag_slugs.len() {
+    return Json(json!({
+        "posts": [],
+        "tags": [],
+        "posters": [],
+        "total_count": 0,
+        "next_cursor": null,
+        "corrected_query": null,
+        "records": [],
+        "highlights": [],
+        "facets": match facet_query.facets {
+          true => Some(PostFacets::default()),
+          false => None,
+        },
+    }))
+    .into_response();
+  }
+
+  let filters = SearchFilters {
+    tag_ids: filter_tags.iter().map(|tag| tag.id).collect(),
+    poster_id: url_query.poster_id,
+    published_after,
+    attributes: attribute_filters,
+  };
+  let pagination = pagination_query.to_db_query();
+
+  let search_result = app_state
     .search_service
-    .search_posts(&url_query.query)
+    .search_posts(
+      &url_query.query,
+      &filters,
+      &url_query.sort,
+      &cursor,
+      &pagination,
+    )
     .await;
-  if !post_ids.is_ok() {
+  if !search_result.is_ok() {
     return StatusCode::INTERNAL_SERVER_ERROR.into_response();
   }
-  let post_ids = post_ids.unwrap();
+  let search_result = search_result.unwrap();
+
+  let facets = match facet_query.facets {
+    true => {
+      let facets = app_state
+        .search_service
+        .get_post_facets(&url_query.query, &filters)
+        .await;
+      if facets.is_err() {
+        // @TODO-ZM: log error reason
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+      }
+      Some(facets.unwrap())
+    }
+    false => None,
+  };
+
+  let next_cursor = match search_result.records.len() as u32 == pagination.limit {
+    true => search_result
+      .records
+      .last()
+      .map(|record| record.to_cursor(search_result.ranked_at)),
+    false => None,
+  };
+
+  let post_ids = search_result
+    .records
+    .iter()
+    .map(|record| record.id)
+    .collect::<Vec<u32>>();
 
   let compact_posts = app_state
     .post_repository
@@ -35,6 +156,18 @@ pub async fn search_posts(
 
   compact_posts.sort_by_key(|post| post_ids.iter().position(|&id| id == post.id).unwrap());
 
+  let highlights = compact_posts
+    .iter()
+    .map(|post| {
+      app_state.search_service.get_search_highlight(
+        post.id,
+        &post.title,
+        &post.short_description,
+        &search_result.search_queries,
+      )
+    })
+    .collect::<Vec<SearchHighlight>>();
+
   let mut unique_tag_ids: Vec<u32> = Vec::new();
   let mut unique_poster_ids: Vec<u32> = Vec::new();
 
@@ -43,6 +176,11 @@ pub async fn search_posts(
     unique_poster_ids.push(post.poster_id);
   }
 
+  // the counted tags are returned along the posts' ones
+  if let Some(facets) = &facets {
+    unique_tag_ids.append(&mut get_facet_tag_ids(facets));
+  }
+
   sort_and_dedup_vec(&mut unique_tag_ids);
   sort_and_dedup_vec(&mut unique_poster_ids);
 
@@ -70,10 +208,208 @@ pub async fn search_posts(
       "posts": compact_posts,
       "tags": compact_tags,
       "posters": compact_posters,
+      "total_count": search_result.total_count,
+      "next_cursor": next_cursor,
+      "corrected_query": search_result.corrected_query,
+      "records": search_result.records,
+      "highlights": highlights,
+      "facets": facets,
+  }))
+  .into_response()
+}
+
+pub async fn search_accounts(
+  State(app_state): State<AppState>,
+  url_query: Query<TextSearchQuery>,
+  pagination_query: Query<SearchPaginationQuery>,
+) -> impl IntoResponse {
+  let search_result = app_state
+    .search_service
+    .search_accounts(&url_query.query, &pagination_query.to_db_query())
+    .await;
+  if search_result.is_err() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let search_result = search_result.unwrap();
+
+  let account_ids = search_result
+    .records
+    .iter()
+    .map(|record| record.id)
+    .collect::<Vec<u32>>();
+
+  let compact_accounts = app_state
+    .account_repository
+    .get_many_compact_accounts_by_ids(account_ids.clone())
+    .await;
+  if compact_accounts.is_err() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let mut compact_accounts = compact_accounts.unwrap();
+
+  compact_accounts
+    .sort_by_key(|account| account_ids.iter().position(|&id| id == account.id).unwrap());
+
+  // the open positions of each account
+  let compact_posts = app_state
+    .post_repository
+    .get_many_published_compact_posts_by_poster_ids(&account_ids, OPEN_POSITION_COUNT_PER_ACCOUNT)
+    .await;
+  if compact_posts.is_err() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let compact_posts = compact_posts.unwrap();
+
+  let mut unique_tag_ids: Vec<u32> = Vec::new();
+  for post in compact_posts.iter() {
+    unique_tag_ids.append(&mut post.tag_ids.clone());
+  }
+  sort_and_dedup_vec(&mut unique_tag_ids);
+
+  let compact_tags = app_state
+    .tag_repository
+    .get_many_compact_tags_by_ids(&unique_tag_ids)
+    .await;
+  if compact_tags.is_err() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let compact_tags = compact_tags.unwrap();
+
+  Json(json!({
+      "accounts": compact_accounts,
+      "posts": compact_posts,
+      "tags": compact_tags,
+      "total_count": search_result.total_count,
+      "corrected_query": search_result.corrected_query,
+      "records": search_result.records,
+  }))
+  .into_response()
+}
+
+// posts and accounts in one list, ranked by score
+pub async fn search_posts_and_accounts(
+  State(app_state): State<AppState>,
+  url_query: Query<TextSearchQuery>,
+  pagination_query: Query<SearchPaginationQuery>,
+) -> impl IntoResponse {
+  let search_result = app_state
+    .search_service
+    .search_posts_and_accounts(&url_query.query, &pagination_query.to_db_query())
+    .await;
+  if search_result.is_err() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let search_result = search_result.unwrap();
+
+  let post_ids = search_result
+    .records
+    .iter()
+    .filter(|record| record.model_type == SearchModelType::Post)
+    .map(|record| record.id)
+    .collect::<Vec<u32>>();
+
+  let account_ids = search_result
+    .records
+    .iter()
+    .filter(|record| record.model_type == SearchModelType::Account)
+    .map(|record| record.id)
+    .collect::<Vec<u32>>();
+
+  let compact_posts = app_state
+    .post_repository
+    .get_many_compact_posts_by_ids(post_ids)
+    .await;
+  if compact_posts.is_err() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let compact_posts = compact_posts.unwrap();
+
+  let highlights = compact_posts
+    .iter()
+    .map(|post| {
+      app_state.search_service.get_search_highlight(
+        post.id,
+        &post.title,
+        &post.short_description,
+        &search_result.search_queries,
+      )
+    })
+    .collect::<Vec<SearchHighlight>>();
+
+  let mut unique_tag_ids: Vec<u32> = Vec::new();
+  let mut unique_account_ids: Vec<u32> = account_ids;
+
+  for post in compact_posts.iter() {
+    unique_tag_ids.append(&mut post.tag_ids.clone());
+    unique_account_ids.push(post.poster_id);
+  }
+
+  sort_and_dedup_vec(&mut unique_tag_ids);
+  sort_and_dedup_vec(&mut unique_account_ids);
+
+  let compact_tags = app_state
+    .tag_repository
+    .get_many_compact_tags_by_ids(&unique_tag_ids)
+    .await;
+  if compact_tags.is_err() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let compact_tags = compact_tags.unwrap();
+
+  let compact_accounts = app_state
+    .account_repository
+    .get_many_compact_accounts_by_ids(unique_account_ids)
+    .await;
+  if compact_accounts.is_err() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let compact_accounts = compact_accounts.unwrap();
+
+  Json(json!({
+      "records": search_result.records,
+      "posts": compact_posts,
+      "accounts": compact_accounts,
+      "tags": compact_tags,
+      "total_count": search_result.total_count,
+      "corrected_query": search_result.corrected_query,
+      "highlights": highlights,
+  }))
+  .into_response()
+}
+
+pub async fn get_many_suggestions(
+  State(app_state): State<AppState>,
+  url_query: Query<SuggestQuery>,
+) -> impl IntoResponse {
+  if url_query.prefix.chars().count() > MAX_SUGGEST_PREFIX_LENGTH {
+    return StatusCode::BAD_REQUEST.into_response();
+  }
+
+  let suggestions = app_state.search_service.get_suggestions(&url_query.prefix);
+  if suggestions.is_err() {
+    // @TODO-ZM: log error reason
+    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+  }
+  let suggestions = suggestions.unwrap();
+
+  Json(json!({
+      "suggestions": suggestions,
   }))
   .into_response()
 }
 
 pub fn create_search_router() -> Router<AppState> {
-  Router::new().route("/posts", axum::routing::get(search_posts))
+  Router::new()
+    .route("/", axum::routing::get(search_posts_and_accounts))
+    .route("/posts", axum::routing::get(search_posts))
+    .route("/accounts", axum::routing::get(search_accounts))
+    .route("/suggest", axum::routing::get(get_many_suggestions))
 }
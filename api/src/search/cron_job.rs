@@ -1,6 +1,11 @@
 use crate::{
   _entry::state::AppState,
-  _utils::{database::DBOrderDirection, error::BootError},
+  _utils::{
+    database::DBOrderDirection,
+    error::{BootError, SearchError},
+    vec::sort_and_dedup_vec,
+  },
+  post::model::{Post, PostTrait},
   task::model::{DBTask, TaskName, TaskStatus, TaskType},
 };
 use std::{
@@ -35,10 +40,25 @@ async fn run_indexing_cron_job(app_state: AppState) {
     return;
   }
 
+  // an edited post is un-indexed then re-indexed, the un-indexing must go first
+  let undo_indexing_tasks = app_state
+    .task_repository
+    .get_many_pending_indexing_tasks("UndoIndexing", "created_at", DBOrderDirection::DESC, 1, 0)
+    .await;
+  if undo_indexing_tasks.is_err() {
+    tracing::error!("Error while getting un-indexing tasks");
+    return;
+  }
+  if !undo_indexing_tasks.unwrap().is_empty() {
+    tracing::info!("⏭️  Waiting for un-indexing tasks, skipping");
+    return;
+  }
+
   tracing::info!("Found {} indexing tasks", tasks.len());
 
   let mut task_ids: Vec<u32> = [].to_vec();
   let mut post_ids = vec![];
+  let mut indexed_account_ids = vec![];
   for task in tasks {
     task_ids.push(task.id);
     match task.name {
@@ -48,13 +68,43 @@ async fn run_indexing_cron_job(app_state: AppState) {
       } => {
         if model_name == "post" {
           post_ids.push(model_id.clone());
+        } else if model_name == "account" {
+          indexed_account_ids.push(model_id.clone());
         }
       }
       _ => {}
     }
   }
 
-  tracing::info!("indexing {} posts", post_ids.len());
+  tracing::info!(
+    "indexing {} posts and {} accounts",
+    post_ids.len(),
+    indexed_account_ids.len()
+  );
+
+  if !indexed_account_ids.is_empty() {
+    let indexed_accounts = app_state
+      .account_repository
+      .get_many_compact_accounts_by_ids(indexed_account_ids)
+      .await;
+    if indexed_accounts.is_err() {
+      tracing::error!("Error while getting accounts to index");
+      return;
+    }
+    let indexed_accounts = indexed_accounts.unwrap();
+
+    let indexing_result = app_state
+      .search_service
+      .index_accounts(indexed_accounts)
+      .await;
+    if indexing_result.is_err() {
+      tracing::error!(
+        "Error while indexing accounts {:?}",
+        indexing_result.err().unwrap()
+      );
+      return;
+    }
+  }
 
   let posts = app_state
     .post_repository
@@ -64,7 +114,13 @@ async fn run_indexing_cron_job(app_state: AppState) {
     tracing::error!("Error while getting posts");
     return;
   }
-  let posts = posts.unwrap();
+  // a task might be stale, posts that were archived, sent to moderation or not published yet since are left out,
+  // their task is completed all the same
+  let posts = posts
+    .unwrap()
+    .into_iter()
+    .filter(|post| post.is_live())
+    .collect::<Vec<Post>>();
 
   let tag_ids = posts
     .iter()
@@ -97,6 +153,19 @@ async fn run_indexing_cron_job(app_state: AppState) {
   }
   let accounts = accounts.unwrap();
 
+  // posters are re-indexed along their posts, so their searchable names stay as fresh
+  let indexing_result = app_state
+    .search_service
+    .index_accounts(accounts.clone())
+    .await;
+  if indexing_result.is_err() {
+    tracing::error!(
+      "Error while indexing posters {:?}",
+      indexing_result.err().unwrap()
+    );
+    return;
+  }
+
   let indexing_result = app_state
     .search_service
     .index_posts(posts, tags, accounts)
@@ -150,6 +219,226 @@ async fn run_indexing_cron_job(app_state: AppState) {
   tracing::info!("✅ Indexing done");
 }
 
+// posters are only searchable while they have posts in the index, like they became searchable with their first one
+async fn unindex_posts_and_accounts(
+  app_state: &AppState,
+  post_ids: Vec<u32>,
+  mut account_ids: Vec<u32>,
+) -> Result<(), SearchError> {
+  let poster_ids = app_state
+    .search_service
+    .get_many_indexed_poster_ids_by_post_ids(&post_ids)
+    .await?;
+
+  tracing::info!("un-indexing {} posts", post_ids.len());
+  app_state.search_service.unindex_posts(post_ids).await?;
+
+  if !poster_ids.is_empty() {
+    let indexed_poster_ids = app_state
+      .search_service
+      .get_many_indexed_poster_ids()
+      .await?;
+    account_ids.extend(
+      poster_ids
+        .into_iter()
+        .filter(|poster_id| !indexed_poster_ids.contains(poster_id)),
+    );
+  }
+  sort_and_dedup_vec(&mut account_ids);
+
+  tracing::info!("un-indexing {} accounts", account_ids.len());
+  app_state.search_service.unindex_accounts(account_ids).await
+}
+
+async fn run_unindexing_cron_job(app_state: AppState) {
+  tracing::info!("🚀 Un-indexing");
+
+  let tasks = app_state
+    .task_repository
+    .get_many_pending_indexing_tasks("UndoIndexing", "created_at", DBOrderDirection::DESC, 10, 0)
+    .await;
+
+  if tasks.is_err() {
+    tracing::error!("Error while getting un-indexing tasks");
+    return;
+  }
+  let tasks = tasks.unwrap();
+
+  if tasks.is_empty() {
+    tracing::info!("⏭️  No un-indexing tasks found, skipping");
+    return;
+  }
+
+  tracing::info!("Found {} un-indexing tasks", tasks.len());
+
+  let mut task_ids: Vec<u32> = [].to_vec();
+  let mut post_ids = vec![];
+  let mut unindexed_account_ids = vec![];
+  for task in tasks {
+    task_ids.push(task.id);
+    match task.name {
+      TaskName::UndoIndexing {
+        model_name,
+        model_id,
+      } => {
+        if model_name == "post" {
+          post_ids.push(model_id.clone());
+        } else if model_name == "account" {
+          unindexed_account_ids.push(model_id.clone());
+        }
+      }
+      _ => {}
+    }
+  }
+
+  let unindexing_result =
+    unindex_posts_and_accounts(&app_state, post_ids, unindexed_account_ids).await;
+  if unindexing_result.is_err() {
+    tracing::error!(
+      "Error while un-indexing posts and accounts {:?}",
+      unindexing_result.err().unwrap()
+    );
+
+    let task_status_update_result = app_state
+      .task_repository
+      .fail_many_tasks_by_ids(task_ids, "Error while un-indexing posts and accounts")
+      .await;
+    if task_status_update_result.is_err() {
+      tracing::error!("Error while failing un-indexing tasks");
+    }
+    return;
+  }
+
+  let task_status_update_result = app_state
+    .task_repository
+    .complete_many_tasks_by_ids(task_ids)
+    .await;
+
+  if task_status_update_result.is_err() {
+    tracing::error!("Error while updating un-indexing tasks");
+    return;
+  }
+
+  let more_tasks = app_state
+    .task_repository
+    .get_many_pending_indexing_tasks("UndoIndexing", "created_at", DBOrderDirection::DESC, 1, 0)
+    .await;
+
+  if more_tasks.is_err() {
+    tracing::error!("Error while getting more un-indexing tasks");
+    return;
+  }
+  let more_tasks = more_tasks.unwrap();
+
+  if more_tasks.is_empty() {
+    tracing::info!("No more un-indexing tasks found, creating bk-tree refreshing task");
+
+    let task_id = app_state
+      .task_repository
+      .create_one_task(DBTask {
+        name: TaskName::RefreshingBKTree,
+        status: TaskStatus::Pending,
+        r#type: TaskType::Automated,
+      })
+      .await;
+
+    if task_id.is_err() {
+      tracing::error!("Error while creating bk-tree refreshing task");
+    }
+  }
+
+  tracing::info!("✅ Un-indexing done");
+}
+
+// fills an empty search index, eg: after switching the search backend
+async fn run_reindexing_cron_job(app_state: AppState) {
+  tracing::info!("🚀 Re-indexing");
+
+  let indexed_post_count = app_state.search_service.get_indexed_post_count().await;
+  if indexed_post_count.is_err() {
+    tracing::error!("Error while getting indexed post count");
+    return;
+  }
+  let indexed_post_count = indexed_post_count.unwrap();
+
+  if indexed_post_count > 0 {
+    tracing::info!(
+      "⏭️  Search index already has {} posts, skipping",
+      indexed_post_count
+    );
+    return;
+  }
+
+  // @TODO-ZM: fetch post count
+  let posts = app_state
+    .post_repository
+    .get_many_published_compact_posts("published_at", DBOrderDirection::DESC, 1_000_000, 0)
+    .await;
+  if posts.is_err() {
+    tracing::error!("Error while getting published posts");
+    return;
+  }
+  let posts = posts.unwrap();
+
+  if posts.is_empty() {
+    tracing::info!("⏭️  No published posts found, skipping");
+    return;
+  }
+
+  tracing::info!("Creating indexing tasks for {} posts", posts.len());
+
+  let mut poster_ids = posts
+    .iter()
+    .map(|post| post.poster_id)
+    .collect::<Vec<u32>>();
+  sort_and_dedup_vec(&mut poster_ids);
+
+  for post in posts {
+    let task_id = app_state
+      .task_repository
+      .create_one_task(DBTask {
+        name: TaskName::Indexing {
+          model_name: "post".to_string(),
+          model_id: post.id,
+        },
+        status: TaskStatus::Pending,
+        r#type: TaskType::Automated,
+      })
+      .await;
+
+    if task_id.is_err() {
+      tracing::error!("Error while creating indexing task for post {}", post.id);
+      return;
+    }
+  }
+
+  tracing::info!("Creating indexing tasks for {} posters", poster_ids.len());
+
+  for poster_id in poster_ids {
+    let task_id = app_state
+      .task_repository
+      .create_one_task(DBTask {
+        name: TaskName::Indexing {
+          model_name: "account".to_string(),
+          model_id: poster_id,
+        },
+        status: TaskStatus::Pending,
+        r#type: TaskType::Automated,
+      })
+      .await;
+
+    if task_id.is_err() {
+      tracing::error!(
+        "Error while creating indexing task for account {}",
+        poster_id
+      );
+      return;
+    }
+  }
+
+  tracing::info!("✅ Re-indexing tasks created");
+}
+
 async fn run_bk_tree_refresher_cron_job(app_state: AppState, has_job_ran_once: bool) {
   tracing::info!("🚀 Refreshing bk-tree");
 
@@ -193,6 +482,49 @@ async fn run_bk_tree_refresher_cron_job(app_state: AppState, has_job_ran_once: b
     return;
   }
 
+  tracing::info!("Refreshing suggestion trie");
+
+  let tags = app_state.tag_repository.get_many_compact_tags().await;
+  if tags.is_err() {
+    tracing::error!("Error while getting tags for the suggestion trie");
+    return;
+  }
+  let tags = tags.unwrap();
+
+  let poster_ids = app_state.search_service.get_many_indexed_poster_ids().await;
+  if poster_ids.is_err() {
+    tracing::error!("Error while getting poster ids for the suggestion trie");
+    return;
+  }
+  let poster_ids = poster_ids.unwrap();
+
+  let posters = match poster_ids.is_empty() {
+    true => Ok(vec![]),
+    false => {
+      app_state
+        .account_repository
+        .get_many_compact_accounts_by_ids(poster_ids)
+        .await
+    }
+  };
+  if posters.is_err() {
+    tracing::error!("Error while getting posters for the suggestion trie");
+    return;
+  }
+  let posters = posters.unwrap();
+
+  let trie_refreshing_result = app_state
+    .search_service
+    .refresh_suggestion_trie(tags, posters)
+    .await;
+  if trie_refreshing_result.is_err() {
+    tracing::error!(
+      "Error while refreshing suggestion trie {:?}",
+      trie_refreshing_result.err().unwrap()
+    );
+    return;
+  }
+
   let task_status_update_result = app_state
     .task_repository
     .complete_many_tasks_by_ids(task_ids)
@@ -203,7 +535,7 @@ async fn run_bk_tree_refresher_cron_job(app_state: AppState, has_job_ran_once: b
     return;
   }
 
-  tracing::info!("✅ Refreshing bk-tree done");
+  tracing::info!("✅ Refreshing bk-tree and suggestion trie done");
 }
 
 impl SearchCronJob {
@@ -236,6 +568,55 @@ impl SearchCronJob {
     Ok(job)
   }
 
+  pub fn create_unindexing_cron_job(&self) -> Result<Job, BootError> {
+    let app_state = self.app_state.clone();
+    let is_job_running = Arc::new(AtomicBool::new(false));
+
+    let job = Job::new_repeated_async(Duration::from_secs(5), move |_, __| {
+      let app_state = app_state.clone();
+      let is_job_running = is_job_running.clone();
+
+      return Box::pin(async move {
+        let compare_and_swap_result =
+          is_job_running.compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed);
+        if compare_and_swap_result.is_ok() && compare_and_swap_result.unwrap() == false {
+          run_unindexing_cron_job(app_state.clone()).await;
+          is_job_running.store(false, Ordering::Relaxed);
+        } else {
+          tracing::info!("⏳ Still un-indexing... ");
+        }
+      });
+    });
+
+    if job.is_err() {
+      tracing::error!("Error while creating un-indexing cron job");
+      return Err(BootError::CronJobSetupError);
+    }
+    let job = job.unwrap();
+
+    Ok(job)
+  }
+
+  pub fn create_reindexing_cron_job(&self) -> Result<Job, BootError> {
+    let app_state = self.app_state.clone();
+
+    let job = Job::new_one_shot_async(Duration::from_secs(0), move |_, __| {
+      let app_state = app_state.clone();
+
+      return Box::pin(async move {
+        run_reindexing_cron_job(app_state).await;
+      });
+    });
+
+    if job.is_err() {
+      tracing::error!("Error while creating re-indexing cron job");
+      return Err(BootError::CronJobSetupError);
+    }
+    let job = job.unwrap();
+
+    Ok(job)
+  }
+
   pub fn create_bk_tree_refresher_cron_job(&self) -> Result<Job, BootError> {
     let app_state = self.app_state.clone();
     let is_job_running = Arc::new(AtomicBool::new(false));
@@ -1,4 +1,7 @@
+pub mod backend;
 pub mod controller;
 pub mod cron_job;
 pub mod model;
 pub mod service;
+pub mod stemmer;
+pub mod trie;
@@ -1,7 +1,146 @@
 use serde::{Deserialize, Serialize};
+use strum_macros::Display;
 
-#[derive(Serialize, Deserialize, Debug)]
+use crate::post::model::PostAttributeFilters;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SearchRecord {
   pub id: u32,
+  pub score: f64,
+  // what the results are sorted by, depends on the SearchSort
+  pub rank: f64,
+}
+
+pub trait SearchRecordTrait {
+  fn to_cursor(&self, ranked_at: f64) -> String;
+}
+
+impl SearchRecordTrait for SearchRecord {
+  // results are sorted by rank then id, so the last record of a page is enough to resume from,
+  // as long as the next pages are ranked at the same time
+  fn to_cursor(&self, ranked_at: f64) -> String {
+    format!("{}_{}_{}", self.rank, self.id, ranked_at)
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchCursor {
+  pub rank: f64,
+  pub id: u32,
+  // julian day the first page was ranked at, see SearchResult
+  pub ranked_at: f64,
+}
+
+pub fn parse_search_cursor(cursor: &str) -> Option<SearchCursor> {
+  let (rest, ranked_at) = cursor.rsplit_once('_')?;
+  let (rank, id) = rest.rsplit_once('_')?;
+  let rank = rank.parse::<f64>().ok().filter(|rank| rank.is_finite())?;
+  let id = id.parse::<u32>().ok()?;
+  let ranked_at = ranked_at
+    .parse::<f64>()
+    .ok()
+    .filter(|ranked_at| ranked_at.is_finite())?;
+
+  Some(SearchCursor {
+    rank,
+    id,
+    ranked_at,
+  })
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchSort {
+  Relevance,
+  Date,
+  // relevance decayed by the post's age
+  #[default]
+  Blend,
+}
+
+#[derive(Debug, Default)]
+pub struct SearchFilters {
+  pub tag_ids: Vec<u32>,
+  pub poster_id: Option<u32>,
+  pub published_after: Option<String>,
+  pub attributes: PostAttributeFilters,
+}
+
+pub struct SearchResult {
+  pub records: Vec<SearchRecord>,
+  pub total_count: u32,
+  // set when the query had misspelled words, the correction being searched along the original query
+  pub corrected_query: Option<String>,
+  pub search_queries: Vec<ParsedSearchQuery>,
+  // julian day the blended ranks were decayed to, otherwise a post's rank drops between two pages
+  pub ranked_at: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SearchModelType {
+  Post,
+  Account,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TypedSearchRecord {
+  pub model_type: SearchModelType,
+  pub id: u32,
+  pub score: f64,
+}
+
+// posts and accounts ranked together by score
+pub struct TypedSearchResult {
+  pub records: Vec<TypedSearchRecord>,
+  pub total_count: u32,
+  pub corrected_query: Option<String>,
+  pub search_queries: Vec<ParsedSearchQuery>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SearchHighlight {
+  pub post_id: u32,
+  pub title: String,
+  pub short_description: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedSearchQuery {
+  pub words: Vec<String>,
+  pub phrases: Vec<Vec<String>>,
+  // stems of both the words and the phrases' words
+  pub stems: Vec<String>,
+}
+
+pub trait ParsedSearchQueryTrait {
+  fn get_all_words(&self) -> Vec<String>;
+}
+
+impl ParsedSearchQueryTrait for ParsedSearchQuery {
+  fn get_all_words(&self) -> Vec<String> {
+    self
+      .words
+      .iter()
+      .chain(self.phrases.iter().flatten())
+      .map(|word| word.clone())
+      .collect()
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(tag = "type")] // to flatten the enum to the parent struct
+pub enum SuggestionType {
+  Tag { slug: String },
+  Poster { poster_id: u32 },
+  Word,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Suggestion {
+  pub text: String,
+  #[serde(flatten)]
+  pub r#type: SuggestionType,
   pub score: u32,
 }
@@ -1,212 +1,768 @@
 use bk_tree::{metrics, BKTree};
-use itertools::Itertools;
-use serde::{Deserialize, Serialize};
 use sqlx::{Pool, QueryBuilder, Row, Sqlite};
-use std::sync::{Arc, Mutex};
+use std::{
+  collections::{HashMap, HashSet},
+  sync::{Arc, Mutex},
+};
 
+use super::{
+  backend::{fts::FTSSearchBackend, word::WordSearchBackend, SearchBackend},
+  model::{
+    ParsedSearchQuery, ParsedSearchQueryTrait, SearchCursor, SearchFilters, SearchHighlight,
+    SearchModelType, SearchRecord, SearchResult, SearchSort, Suggestion, SuggestionType,
+    TypedSearchRecord, TypedSearchResult,
+  },
+  stemmer::Stemmer,
+  trie::SuggestionTrie,
+};
 use crate::{
   _utils::{
     error::SearchError,
-    string::{escape_double_quote, get_searchable_words, get_words},
+    markdown::get_markdown_text,
+    query::DBPaginationQuery,
+    string::{escape_html, get_searchable_words, get_words, normalize_text},
   },
   account::model::{AccountNameTrait, CompactAccount},
-  post::model::Post,
-  tag::model::CompactTag,
+  config::service::{ConfigService, SearchBackendName},
+  post::{
+    model::{Post, PostFacets},
+    repository::{get_post_facets_from_rows, push_post_attribute_filters, push_post_facets_query},
+  },
+  tag::model::{CompactTag, Tag},
 };
 
-#[derive(Debug, Serialize, Deserialize)]
-struct WordIndex {
-  word: String,
-  model_id: u32,
-  appear_in: String,
+// bounds the bk-tree lookups and the number of bound parameters per search
+const MAX_QUERY_WORDS: usize = 50;
+const MAX_QUERY_WORD_LENGTH: usize = 64;
+
+const MAX_TITLE_SNIPPET_LENGTH: usize = 120;
+const MAX_SHORT_DESCRIPTION_SNIPPET_LENGTH: usize = 200;
+
+const MAX_SUGGESTED_TITLE_WORDS: u32 = 10_000;
+// a tag or a poster name is a better completion than a single title word used as often
+const TAG_SUGGESTION_WEIGHT: u32 = 3;
+const POSTER_SUGGESTION_WEIGHT: u32 = 2;
+const WORD_SUGGESTION_WEIGHT: u32 = 1;
+
+// "Software Engineering" can be completed from "soft" as well as from "engin"
+fn get_suggestion_keys(text: &String) -> Vec<String> {
+  let normalized_text = normalize_text(text);
+  let words = get_words(&normalized_text).collect::<Vec<&str>>();
+
+  (0..words.len())
+    .map(|index| words[index..].join(" "))
+    .collect()
+}
+
+fn get_query_words(paragraph: &String) -> Vec<String> {
+  get_searchable_words(paragraph)
+    .into_iter()
+    .filter(|word| word.chars().count() <= MAX_QUERY_WORD_LENGTH)
+    .collect()
+}
+
+fn create_search_query(
+  words: Vec<String>,
+  phrases: Vec<Vec<String>>,
+  stemmer: &Stemmer,
+) -> ParsedSearchQuery {
+  let mut stems = vec![];
+  for word in words.iter().chain(phrases.iter().flatten()) {
+    let stem = stemmer.stem(word);
+    if !stems.contains(&stem) {
+      stems.push(stem);
+    }
+  }
+
+  ParsedSearchQuery {
+    words,
+    phrases,
+    stems,
+  }
+}
+
+// text between double quotes is searched as a phrase, the rest as separate words
+fn parse_query(query: &String, stemmer: &Stemmer) -> ParsedSearchQuery {
+  let mut words = vec![];
+  let mut phrases = vec![];
+
+  for (index, part) in query.split('"').enumerate() {
+    let part_words = get_query_words(&part.to_string());
+    if !index.is_multiple_of(2) && part_words.len() > 1 {
+      phrases.push(part_words);
+    } else {
+      words.extend(part_words);
+    }
+  }
+
+  let mut remaining_words = MAX_QUERY_WORDS;
+  words.truncate(remaining_words);
+  remaining_words -= words.len();
+
+  let phrases = phrases
+    .into_iter()
+    .filter(|phrase: &Vec<String>| {
+      if phrase.len() > remaining_words {
+        return false;
+      }
+      remaining_words -= phrase.len();
+      true
+    })
+    .collect();
+
+  create_search_query(words, phrases, stemmer)
+}
+
+// the outer query of a search, the scored posts are pushed by the backend in between
+fn push_filtered_search_query(
+  query_builder: &mut QueryBuilder<'_, Sqlite>,
+  filters: &SearchFilters,
+) {
+  query_builder.push(
+    r#"
+    ) AS scored_post
+    JOIN indexed_post ON indexed_post.id = scored_post.model_id
+    WHERE 1 = 1"#,
+  );
+
+  if let Some(poster_id) = filters.poster_id {
+    query_builder.push(" AND indexed_post.poster_id = ");
+    query_builder.push_bind(poster_id);
+  }
+
+  if let Some(published_after) = &filters.published_after {
+    query_builder.push(" AND indexed_post.published_at > ");
+    query_builder.push_bind(published_after.clone());
+  }
+
+  if !filters.tag_ids.is_empty() {
+    query_builder
+      .push(" AND indexed_post.id IN (SELECT post_id FROM indexed_post_tag WHERE tag_id IN (");
+    let mut separated = query_builder.separated(", ");
+    for tag_id in &filters.tag_ids {
+      separated.push_bind(*tag_id);
+    }
+    separated.push_unseparated(") GROUP BY post_id HAVING COUNT(*) = ");
+    query_builder.push_bind(filters.tag_ids.len() as i64);
+    query_builder.push(")");
+  }
+
+  push_post_attribute_filters(query_builder, "indexed_post", &filters.attributes);
+}
+
+// splits a text into its words and the separators between them, flagging the words matching the search
+fn get_highlight_segments(
+  text: &String,
+  search_queries: &Vec<ParsedSearchQuery>,
+  stemmer: &Stemmer,
+) -> Vec<(String, bool)> {
+  let words = search_queries
+    .iter()
+    .flat_map(|search_query| search_query.get_all_words())
+    .collect::<HashSet<String>>();
+  let stems = search_queries
+    .iter()
+    .flat_map(|search_query| search_query.stems.clone())
+    .collect::<HashSet<String>>();
+
+  let mut segments: Vec<(String, bool)> = vec![];
+  let mut segment = String::new();
+  let mut is_segment_word = false;
+  for c in text.chars().chain(std::iter::once('\0')) {
+    if c.is_alphanumeric() != is_segment_word || c == '\0' {
+      if !segment.is_empty() {
+        let is_match = is_segment_word && {
+          let normalized_word = normalize_text(&segment);
+          words.contains(&normalized_word) || stems.contains(&stemmer.stem(&normalized_word))
+        };
+        segments.push((segment, is_match));
+      }
+      segment = String::new();
+      is_segment_word = c.is_alphanumeric();
+    }
+    segment.push(c);
+  }
+
+  segments
+}
+
+// escapes the text and wraps the matched words in <mark> tags,
+// long texts are cut around the first match
+fn get_highlighted_snippet(segments: Vec<(String, bool)>, max_length: usize) -> String {
+  let first_match_index = segments
+    .iter()
+    .position(|(_, is_match)| *is_match)
+    .unwrap_or(0);
+
+  // starts a bit before the first match to give it some context
+  let mut start = first_match_index;
+  let mut length = 0;
+  while start > 0 && length + segments[start - 1].0.chars().count() <= max_length / 4 {
+    start -= 1;
+    length += segments[start].0.chars().count();
+  }
+
+  let mut end = start;
+  let mut length = 0;
+  while end < segments.len() && length + segments[end].0.chars().count() <= max_length {
+    length += segments[end].0.chars().count();
+    end += 1;
+  }
+
+  let mut snippet = String::new();
+  if start > 0 {
+    snippet.push('…');
+  }
+  for (segment, is_match) in &segments[start..end] {
+    match is_match {
+      true => snippet.push_str(&format!("<mark>{}</mark>", escape_html(segment))),
+      false => snippet.push_str(&escape_html(segment)),
+    }
+  }
+  if end < segments.len() {
+    snippet.push('…');
+  }
+
+  snippet
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct WordRecord {
-  word: String,
+// everything a spelling correction needs, rebuilt and swapped as a whole
+struct CorrectionIndex {
+  // holds stems, corrections are shown to users as one of the words sharing the stem
+  bk_tree: BKTree<String>,
+  display_words: HashMap<String, String>,
+  // number of posts a stem appears in, to prefer common words among equally close corrections
+  frequencies: HashMap<String, u32>,
+}
+
+impl CorrectionIndex {
+  fn new() -> Self {
+    Self {
+      bk_tree: BKTree::new(metrics::Levenshtein),
+      display_words: HashMap::new(),
+      frequencies: HashMap::new(),
+    }
+  }
+}
+
+// same as SQLite's julianday('now'), but can be kept in a cursor
+fn get_current_julian_day() -> f64 {
+  chrono::Utc::now().timestamp_millis() as f64 / 86_400_000.0 + 2_440_587.5
+}
+
+// pushes the "score" and "rank" columns of a scored post joined with its indexed_post,
+// published_at is cut to the seconds as the stored fractional part isn't always a valid SQLite date,
+// the age decay is hyperbolic rather than exponential, as SQLite may lack math functions,
+// it still halves the score of a post as old as the half-life
+fn push_ranking_columns(
+  query_builder: &mut QueryBuilder<'_, Sqlite>,
+  sort: &SearchSort,
+  recency_half_life_days: f64,
+  ranked_at: f64,
+) {
+  let push_decayed_score = |query_builder: &mut QueryBuilder<'_, Sqlite>| {
+    query_builder.push("scored_post.score / (1.0 + MAX(COALESCE(");
+    query_builder.push_bind(ranked_at);
+    query_builder.push(" - julianday(substr(indexed_post.published_at, 1, 19)), 0.0), 0.0) / ");
+    query_builder.push_bind(recency_half_life_days);
+    query_builder.push(")");
+  };
+
+  match sort {
+    SearchSort::Relevance => {
+      query_builder.push("scored_post.score AS score, scored_post.score AS rank");
+    }
+    SearchSort::Date => {
+      query_builder.push(
+        "scored_post.score AS score, COALESCE(julianday(substr(indexed_post.published_at, 1, 19)), 0.0) AS rank",
+      );
+    }
+    SearchSort::Blend => {
+      push_decayed_score(query_builder);
+      query_builder.push(" AS score, ");
+      push_decayed_score(query_builder);
+      query_builder.push(" AS rank");
+    }
+  }
 }
 
 pub struct SearchService {
   search_sql_db: Arc<Pool<Sqlite>>,
-  bk_tree: Arc<Mutex<BKTree<String>>>,
+  backend: Box<dyn SearchBackend>,
+  stemmer: Stemmer,
+  recency_half_life_days: f64,
+  correction_index: Arc<Mutex<CorrectionIndex>>,
+  suggestion_trie: Arc<Mutex<SuggestionTrie>>,
 }
 
 impl SearchService {
-  pub fn new(search_sql_db: Arc<Pool<Sqlite>>) -> Self {
+  pub fn new(config_service: Arc<ConfigService>, search_sql_db: Arc<Pool<Sqlite>>) -> Self {
+    let config = config_service.get_config();
+    let stemmer = Stemmer {
+      english: config.search_english_stemmer,
+      french: config.search_french_stemmer,
+    };
+
+    let backend: Box<dyn SearchBackend> = match config.search_backend {
+      SearchBackendName::Word => Box::new(WordSearchBackend::new(
+        Arc::clone(&search_sql_db),
+        stemmer.clone(),
+      )),
+      SearchBackendName::Fts => Box::new(FTSSearchBackend::new(
+        Arc::clone(&search_sql_db),
+        stemmer.clone(),
+      )),
+    };
+
     Self {
       search_sql_db,
-      bk_tree: Arc::new(Mutex::new(BKTree::new(metrics::Levenshtein))),
+      backend,
+      stemmer,
+      recency_half_life_days: config.search_recency_half_life_days,
+      correction_index: Arc::new(Mutex::new(CorrectionIndex::new())),
+      suggestion_trie: Arc::new(Mutex::new(SuggestionTrie::new())),
     }
   }
 
+  // the new index is built before taking the lock, so searches are never blocked by a rebuild,
+  // and words of deleted or edited posts don't outlive it
   pub async fn refresh_bk_tree(&self) -> Result<(), SearchError> {
+    let words = self.backend.get_many_correction_words().await?;
+
+    let mut new_correction_index = CorrectionIndex::new();
+    for (word, frequency) in words {
+      let stem = self.stemmer.stem(&word);
+
+      *new_correction_index
+        .frequencies
+        .entry(stem.clone())
+        .or_insert(0) += frequency;
+
+      // prefers the shortest actual word over a bare stem, eg: "developer" over "developers" and "develop"
+      let is_better_display_word = match new_correction_index.display_words.get(&stem) {
+        Some(display_word) => {
+          (word == stem, word.len()) < (*display_word == stem, display_word.len())
+        }
+        None => true,
+      };
+      if is_better_display_word {
+        new_correction_index.display_words.insert(stem, word);
+      }
+    }
+
+    for stem in new_correction_index.display_words.keys() {
+      new_correction_index.bk_tree.add(stem.clone());
+    }
+
+    let correction_index = self.correction_index.lock();
+    if correction_index.is_err() {
+      tracing::error!(
+        "Error while getting correction index lock: {:?}",
+        correction_index.err()
+      );
+      return Err(SearchError::InternalError);
+    }
+    *correction_index.unwrap() = new_correction_index;
+
+    Ok(())
+  }
+
+  // the posters of the given posts, among the indexed ones
+  pub async fn get_many_indexed_poster_ids_by_post_ids(
+    &self,
+    post_ids: &Vec<u32>,
+  ) -> Result<Vec<u32>, SearchError> {
+    if post_ids.is_empty() {
+      return Ok(vec![]);
+    }
+
     let conn = self.search_sql_db.acquire().await;
     if conn.is_err() {
       tracing::error!(
-        "Error while getting sql connection to refresh bk tree: {:?}",
+        "Error while getting sql connection to get poster ids: {:?}",
         conn
       );
       return Err(SearchError::InternalError);
     }
     let mut conn = conn.unwrap();
 
-    // @TODO-ZM: figure out how query $ replacement work, there is some unneeded "magic" here
-    let db_result = sqlx::query(
-      r#"
-        SELECT DISTINCT word
-        FROM word
-        WHERE appear_in IN ('post_title', 'post_short_description', 'post_tag_name', 'post_poster_display_name');
-      "#,
+    let mut query_builder =
+      QueryBuilder::<Sqlite>::new("SELECT DISTINCT poster_id FROM indexed_post WHERE id IN (");
+    let mut separated = query_builder.separated(", ");
+    for post_id in post_ids {
+      separated.push_bind(*post_id);
+    }
+    separated.push_unseparated(")");
+
+    let db_result = query_builder.build().fetch_all(&mut *conn).await;
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting indexed poster ids by post ids: {:?}",
+        db_result.err()
+      );
+      return Err(SearchError::InternalError);
+    }
+
+    Ok(
+      db_result
+        .unwrap()
+        .iter()
+        .map(|row| row.get::<u32, _>("poster_id"))
+        .collect(),
     )
-    .fetch_all(&mut *conn)
-    .await;
+  }
 
+  pub async fn get_many_indexed_poster_ids(&self) -> Result<Vec<u32>, SearchError> {
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!(
+        "Error while getting sql connection to get poster ids: {:?}",
+        conn
+      );
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query("SELECT DISTINCT poster_id FROM indexed_post")
+      .fetch_all(&mut *conn)
+      .await;
     if db_result.is_err() {
-      tracing::error!("Error while getting all words: {:?}", db_result.err());
+      tracing::error!(
+        "Error while getting indexed poster ids: {:?}",
+        db_result.err()
+      );
       return Err(SearchError::InternalError);
     }
-    let db_result = db_result.unwrap();
 
-    let mut words = vec![];
+    Ok(
+      db_result
+        .unwrap()
+        .iter()
+        .map(|row| row.get::<u32, _>("poster_id"))
+        .collect(),
+    )
+  }
 
-    for row in db_result {
-      words.push(row.get::<String, _>("word"));
+  async fn get_many_indexed_post_counts(
+    &self,
+    group_by_column: &str,
+    table: &str,
+  ) -> Result<HashMap<u32, u32>, SearchError> {
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!(
+        "Error while getting sql connection to count posts: {:?}",
+        conn
+      );
+      return Err(SearchError::InternalError);
     }
+    let mut conn = conn.unwrap();
 
-    let bk_tree = self.bk_tree.lock();
-    if bk_tree.is_err() {
-      tracing::error!("Error while getting bk tree lock: {:?}", bk_tree.err());
+    let db_result = sqlx::query(&format!(
+      "SELECT {} AS id, COUNT(*) AS count FROM {} GROUP BY {}",
+      group_by_column, table, group_by_column
+    ))
+    .fetch_all(&mut *conn)
+    .await;
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while counting posts by {}: {:?}",
+        group_by_column,
+        db_result.err()
+      );
       return Err(SearchError::InternalError);
     }
-    let mut bk_tree = bk_tree.unwrap();
-    // @TODO-ZM: clean the tree before adding new words
 
-    for word in words {
-      bk_tree.add(word);
+    Ok(
+      db_result
+        .unwrap()
+        .iter()
+        .map(|row| (row.get::<u32, _>("id"), row.get::<i64, _>("count") as u32))
+        .collect(),
+    )
+  }
+
+  // the new trie is built before taking the lock, so lookups are never blocked by a refresh
+  pub async fn refresh_suggestion_trie(
+    &self,
+    tags: Vec<Tag>,
+    posters: Vec<CompactAccount>,
+  ) -> Result<(), SearchError> {
+    let tag_post_counts = self
+      .get_many_indexed_post_counts("tag_id", "indexed_post_tag")
+      .await?;
+    let poster_post_counts = self
+      .get_many_indexed_post_counts("poster_id", "indexed_post")
+      .await?;
+    let title_word_frequencies = self
+      .backend
+      .get_many_title_word_frequencies(MAX_SUGGESTED_TITLE_WORDS)
+      .await?;
+
+    let mut suggestion_trie = SuggestionTrie::new();
+
+    for tag in tags {
+      // tags without posts are still worth suggesting when posting a job
+      let post_count = tag_post_counts.get(&tag.id).unwrap_or(&0) + 1;
+      let suggestion = Suggestion {
+        text: tag.name.clone(),
+        r#type: SuggestionType::Tag { slug: tag.slug },
+        score: post_count * TAG_SUGGESTION_WEIGHT,
+      };
+      for key in get_suggestion_keys(&tag.name) {
+        suggestion_trie.insert(&key, suggestion.clone());
+      }
+    }
+
+    for poster in posters {
+      let display_name = poster.get_display_name();
+      let post_count = poster_post_counts.get(&poster.id).unwrap_or(&0) + 1;
+      let suggestion = Suggestion {
+        text: display_name.clone(),
+        r#type: SuggestionType::Poster {
+          poster_id: poster.id,
+        },
+        score: post_count * POSTER_SUGGESTION_WEIGHT,
+      };
+      for key in get_suggestion_keys(&display_name) {
+        suggestion_trie.insert(&key, suggestion.clone());
+      }
+    }
+
+    for (word, frequency) in title_word_frequencies {
+      suggestion_trie.insert(
+        &word,
+        Suggestion {
+          text: word.clone(),
+          r#type: SuggestionType::Word,
+          score: frequency * WORD_SUGGESTION_WEIGHT,
+        },
+      );
     }
 
+    let trie = self.suggestion_trie.lock();
+    if trie.is_err() {
+      tracing::error!("Error while getting suggestion trie lock: {:?}", trie.err());
+      return Err(SearchError::InternalError);
+    }
+    *trie.unwrap() = suggestion_trie;
+
     Ok(())
   }
 
+  pub fn get_suggestions(&self, prefix: &String) -> Result<Vec<Suggestion>, SearchError> {
+    let normalized_prefix = normalize_text(prefix);
+    let mut key = get_words(&normalized_prefix)
+      .collect::<Vec<&str>>()
+      .join(" ");
+    // keeps "software " from completing to "softwares"
+    if normalized_prefix.ends_with(' ') && !key.is_empty() {
+      key.push(' ');
+    }
+
+    if key.is_empty() {
+      return Ok(vec![]);
+    }
+
+    let trie = self.suggestion_trie.lock();
+    if trie.is_err() {
+      tracing::error!("Error while getting suggestion trie lock: {:?}", trie.err());
+      return Err(SearchError::InternalError);
+    }
+
+    Ok(trie.unwrap().find(&key))
+  }
+
   pub async fn index_posts(
     &self,
     posts: Vec<Post>,
     tags: Vec<CompactTag>,
     posters: Vec<CompactAccount>,
   ) -> Result<(), SearchError> {
-    let mut word_indexes: Vec<WordIndex> = vec![];
-    for post in posts {
-      // @TODO-ZM: use regex \d to split the string
-      get_words(&post.title).for_each(|word| {
-        let word = word.to_lowercase();
-
-        word_indexes.push(WordIndex {
-          word,
-          model_id: post.id,
-          appear_in: "post_title".to_string(),
-        });
-      });
-
-      get_words(&post.description).for_each(|word| {
-        let word = word.to_lowercase();
-
-        word_indexes.push(WordIndex {
-          word,
-          model_id: post.id,
-          appear_in: "post_description".to_string(),
-        });
-      });
-
-      for tag_id in post.tag_ids {
-        let tag = tags
-          .iter()
-          .find(|tag| tag.id == tag_id)
-          .map(|tag| tag.clone());
+    self.index_post_filters(&posts, &posters).await?;
 
-        if tag.is_none() {
-          tracing::error!("Failed to find the tag by id {}", tag_id,);
-          return Err(SearchError::InternalError);
-        };
-        let tag = tag.unwrap();
+    // the markup would otherwise be tokenized along the words, eg: "**react**" or a link's url
+    let posts = posts
+      .into_iter()
+      .map(|post| Post {
+        description: get_markdown_text(&post.description),
+        ..post
+      })
+      .collect::<Vec<Post>>();
 
-        get_words(&tag.name).for_each(|word| {
-          let word = word.to_lowercase();
+    self.backend.index_posts(posts, tags, posters).await
+  }
 
-          word_indexes.push(WordIndex {
-            word,
-            model_id: post.id,
-            appear_in: "post_tag_name".to_string(),
-          });
-        });
-      }
+  pub async fn index_accounts(&self, accounts: Vec<CompactAccount>) -> Result<(), SearchError> {
+    self.backend.index_accounts(accounts).await
+  }
 
-      let poster = posters.iter().find(|poster| poster.id == post.poster_id);
-      if poster.is_none() {
-        tracing::error!("Failed to find the poster");
-        return Err(SearchError::InternalError);
-      }
-      let poster = poster.unwrap();
+  pub async fn unindex_posts(&self, post_ids: Vec<u32>) -> Result<(), SearchError> {
+    self.unindex_post_filters(&post_ids).await?;
+    self.backend.unindex_posts(post_ids).await
+  }
 
-      get_words(&poster.get_display_name()).for_each(|word| {
-        let word = word.to_lowercase();
+  pub async fn unindex_accounts(&self, account_ids: Vec<u32>) -> Result<(), SearchError> {
+    self.backend.unindex_accounts(account_ids).await
+  }
 
-        word_indexes.push(WordIndex {
-          word,
-          model_id: post.id,
-          appear_in: "post_poster_display_name".to_string(),
-        });
-      });
+  async fn index_post_filters(
+    &self,
+    posts: &Vec<Post>,
+    posters: &Vec<CompactAccount>,
+  ) -> Result<(), SearchError> {
+    if posts.is_empty() {
+      return Ok(());
     }
 
+    let post_ids = posts.iter().map(|post| post.id).collect::<Vec<u32>>();
+    // drops the tags of re-indexed posts, they might have changed
+    self.unindex_post_filters(&post_ids).await?;
+
     let conn = self.search_sql_db.acquire().await;
     if conn.is_err() {
-      tracing::error!("Error while getting sql connection to index: {:?}", conn);
+      tracing::error!(
+        "Error while getting sql connection to index post filters: {:?}",
+        conn
+      );
       return Err(SearchError::InternalError);
     }
     let mut conn = conn.unwrap();
 
-    let mut query_builder =
-      QueryBuilder::new("INSERT INTO word (word, model_type, model_id, appear_in) ");
-
-    query_builder.push_values(word_indexes, |mut b, new_word_index| {
-      b.push_bind(new_word_index.word)
-        .push_bind("post")
-        .push_bind(new_word_index.model_id)
-        .push_bind(new_word_index.appear_in);
+    let mut query_builder = QueryBuilder::<Sqlite>::new(
+      r#"INSERT INTO indexed_post (id, poster_id, poster_type, published_at, wilaya_code, contract_type,
+        remote_policy, salary_max, salary_currency, salary_period) "#,
+    );
+    query_builder.push_values(posts, |mut b, post| {
+      let salary = post.attributes.salary.as_ref();
+      let poster_type = posters
+        .iter()
+        .find(|poster| poster.id == post.poster_id)
+        .map(|poster| poster.r#type.to_string())
+        .unwrap_or_default();
+      b.push_bind(post.id)
+        .push_bind(post.poster_id)
+        .push_bind(poster_type)
+        .push_bind(post.published_at.clone())
+        .push_bind(post.attributes.wilaya_code)
+        .push_bind(
+          post
+            .attributes
+            .contract_type
+            .as_ref()
+            .map(|contract_type| contract_type.to_string()),
+        )
+        .push_bind(
+          post
+            .attributes
+            .remote_policy
+            .as_ref()
+            .map(|remote_policy| remote_policy.to_string()),
+        )
+        .push_bind(salary.map(|salary| salary.max))
+        .push_bind(salary.map(|salary| salary.currency.to_string()))
+        .push_bind(salary.map(|salary| salary.period.to_string()));
     });
 
     let db_result = query_builder.build().execute(&mut *conn).await;
+    if db_result.is_err() {
+      tracing::error!("Error while indexing post filters: {:?}", db_result);
+      return Err(SearchError::InternalError);
+    }
 
+    let post_tags = posts
+      .iter()
+      .flat_map(|post| post.tag_ids.iter().map(|tag_id| (post.id, *tag_id)))
+      .collect::<Vec<(u32, u32)>>();
+    if post_tags.is_empty() {
+      return Ok(());
+    }
+
+    let mut query_builder =
+      QueryBuilder::<Sqlite>::new("INSERT OR IGNORE INTO indexed_post_tag (post_id, tag_id) ");
+    query_builder.push_values(post_tags, |mut b, (post_id, tag_id)| {
+      b.push_bind(post_id).push_bind(tag_id);
+    });
+
+    let db_result = query_builder.build().execute(&mut *conn).await;
     if db_result.is_err() {
-      tracing::error!("Error while indexing posts: {:?}", db_result);
+      tracing::error!("Error while indexing post tags: {:?}", db_result);
       return Err(SearchError::InternalError);
     }
 
     Ok(())
   }
 
-  fn get_corrected_queries(&self, query: &String, max_suggestions: u8) -> Vec<String> {
-    let query_words = get_searchable_words(query);
+  async fn unindex_post_filters(&self, post_ids: &Vec<u32>) -> Result<(), SearchError> {
+    if post_ids.is_empty() {
+      return Ok(());
+    }
 
-    let bk_tree = self.bk_tree.lock().unwrap();
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!(
+        "Error while getting sql connection to unindex post filters: {:?}",
+        conn
+      );
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    for (table, post_id_column) in [("indexed_post_tag", "post_id"), ("indexed_post", "id")] {
+      let mut query_builder = QueryBuilder::<Sqlite>::new(format!(
+        "DELETE FROM {} WHERE {} IN (",
+        table, post_id_column
+      ));
+      let mut separated = query_builder.separated(", ");
+      for post_id in post_ids {
+        separated.push_bind(*post_id);
+      }
+      separated.push_unseparated(")");
+
+      let db_result = query_builder.build().execute(&mut *conn).await;
+      if db_result.is_err() {
+        tracing::error!("Error while unindexing post filters: {:?}", db_result);
+        return Err(SearchError::InternalError);
+      }
+    }
+
+    Ok(())
+  }
+
+  pub async fn get_indexed_post_count(&self) -> Result<u32, SearchError> {
+    self.backend.get_indexed_post_count().await
+  }
+
+  fn get_corrected_queries(
+    &self,
+    query_words: &Vec<String>,
+    max_suggestions: u8,
+  ) -> Vec<Vec<String>> {
+    let correction_index = self.correction_index.lock().unwrap();
     let mut corrected_words_in_queries = vec![];
-    for query_word in &query_words {
+    for query_word in query_words {
       let mut corrected_words_with_distance = vec![];
 
       let tolerance = query_word.len() / 2;
-      bk_tree
+      correction_index
+        .bk_tree
         .find(query_word, tolerance as u32)
         .for_each(|(distance, corrected_word)| {
           corrected_words_with_distance.push((distance, corrected_word));
         });
 
-      corrected_words_with_distance.sort_by_key(|k| k.0);
+      // among equally close words, the more common one wins
+      corrected_words_with_distance.sort_by_key(|(distance, corrected_word)| {
+        (
+          *distance,
+          std::cmp::Reverse(correction_index.frequencies.get(*corrected_word)),
+        )
+      });
 
       corrected_words_in_queries.push(
         corrected_words_with_distance
           .iter()
-          .map(|(_, corrected_word)| corrected_word)
-          .map(|corrected_word| escape_double_quote(corrected_word))
+          .map(|(_, corrected_word)| corrected_word.to_string())
           .collect::<Vec<String>>(),
       );
     }
@@ -215,37 +771,154 @@ impl SearchService {
 
     for index in 0..max_suggestions {
       let mut corrected_query_words = vec![];
-      for query_word_index in 0..query_words.len() {
+      for (query_word_index, query_word) in query_words.iter().enumerate() {
         let corrected_word = corrected_words_in_queries
           .get(query_word_index)
           .unwrap()
           .get(index as usize);
 
         match corrected_word {
-          Some(corrected_word) => corrected_query_words.push(corrected_word.as_str()),
-          None => corrected_query_words.push({
-            match query_words.get(query_word_index) {
-              Some(query_word) => query_word,
-              None => "",
-            }
-          }),
+          Some(corrected_word) => corrected_query_words.push(corrected_word.clone()),
+          None => corrected_query_words.push(query_word.clone()),
         }
       }
-      corrected_queries.push(corrected_query_words.join(" "));
+      corrected_queries.push(corrected_query_words);
     }
 
-    // filter out the original query
-    let corrected_queries = corrected_queries
+    // the first corrected query is made of the closest words, it's the original query when no word is misspelled
+    corrected_queries
+  }
+
+  // corrections are stems, they're searched as the word shown for them, eg: "developer" for "develop",
+  // so they're stemmed once like any typed word, and the words left as typed are kept as typed
+  fn get_corrected_words(
+    &self,
+    words: &Vec<String>,
+    stems: &Vec<String>,
+    corrected_stems: &Vec<String>,
+  ) -> Vec<String> {
+    let correction_index = self.correction_index.lock().unwrap();
+
+    corrected_stems
+      .iter()
+      .enumerate()
+      .map(
+        |(index, corrected_stem)| match *corrected_stem == stems[index] {
+          true => words[index].clone(),
+          false => correction_index
+            .display_words
+            .get(corrected_stem)
+            .unwrap_or(corrected_stem)
+            .clone(),
+        },
+      )
+      .collect()
+  }
+
+  fn get_display_query(&self, corrected_words: &Vec<String>, phrases: &Vec<Vec<String>>) -> String {
+    corrected_words
+      .iter()
+      .cloned()
+      .chain(
+        phrases
+          .iter()
+          .map(|phrase| format!("\"{}\"", phrase.join(" "))),
+      )
+      .collect::<Vec<String>>()
+      .join(" ")
+  }
+
+  pub fn get_search_highlight(
+    &self,
+    post_id: u32,
+    title: &String,
+    short_description: &String,
+    search_queries: &Vec<ParsedSearchQuery>,
+  ) -> SearchHighlight {
+    SearchHighlight {
+      post_id,
+      title: get_highlighted_snippet(
+        get_highlight_segments(title, search_queries, &self.stemmer),
+        MAX_TITLE_SNIPPET_LENGTH,
+      ),
+      short_description: get_highlighted_snippet(
+        get_highlight_segments(short_description, search_queries, &self.stemmer),
+        MAX_SHORT_DESCRIPTION_SNIPPET_LENGTH,
+      ),
+    }
+  }
+
+  // the parsed query followed by its corrections, and the correction to display if any
+  fn get_search_queries(&self, query: &String) -> Option<(Vec<ParsedSearchQuery>, Option<String>)> {
+    let parsed_query = parse_query(query, &self.stemmer);
+
+    if parsed_query.words.is_empty() && parsed_query.phrases.is_empty() {
+      return None;
+    }
+
+    // corrections are looked up by stem, so "developpers" is corrected the same way as "developper"
+    let query_stems = parsed_query
+      .words
       .iter()
-      .filter(|corrected_query| *corrected_query != query)
-      .map(|corrected_query| corrected_query.to_string())
+      .map(|word| self.stemmer.stem(word))
       .collect::<Vec<String>>();
 
-    corrected_queries
+    let corrected_queries = self.get_corrected_queries(&query_stems, 3);
+    // the closest correction is only shown when it differs from what was typed
+    let is_misspelled = corrected_queries
+      .first()
+      .is_some_and(|corrected_stems| *corrected_stems != query_stems);
+
+    let corrected_queries = corrected_queries
+      .into_iter()
+      .filter(|corrected_stems| *corrected_stems != query_stems)
+      .map(|corrected_stems| {
+        self.get_corrected_words(&parsed_query.words, &query_stems, &corrected_stems)
+      })
+      .collect::<Vec<Vec<String>>>();
+
+    let corrected_query = corrected_queries
+      .first()
+      .filter(|_| is_misspelled)
+      .map(|corrected_words| self.get_display_query(corrected_words, &parsed_query.phrases));
+
+    let mut search_queries = corrected_queries
+      .into_iter()
+      .map(|corrected_words| {
+        create_search_query(corrected_words, parsed_query.phrases.clone(), &self.stemmer)
+      })
+      .collect::<Vec<ParsedSearchQuery>>();
+    search_queries.insert(0, parsed_query);
+
+    Some((search_queries, corrected_query))
   }
 
-  // @TODO-ZM: add pagination
-  pub async fn search_posts(&self, query: &String) -> Result<Vec<u32>, SearchError> {
+  // a cursor takes precedence over the pagination's start
+  pub async fn search_posts(
+    &self,
+    query: &String,
+    filters: &SearchFilters,
+    sort: &SearchSort,
+    cursor: &Option<SearchCursor>,
+    pagination: &DBPaginationQuery,
+  ) -> Result<SearchResult, SearchError> {
+    let ranked_at = match cursor {
+      Some(cursor) => cursor.ranked_at,
+      None => get_current_julian_day(),
+    };
+
+    let search_queries = self.get_search_queries(query);
+    if search_queries.is_none() {
+      return Ok(SearchResult {
+        records: vec![],
+        total_count: 0,
+        corrected_query: None,
+        search_queries: vec![],
+        ranked_at,
+      });
+    }
+    let (search_queries, corrected_query) = search_queries.unwrap();
+
     let conn = self.search_sql_db.acquire().await;
     if conn.is_err() {
       tracing::error!("Error while getting sql connection to search: {:?}", conn);
@@ -253,80 +926,686 @@ impl SearchService {
     }
     let mut conn = conn.unwrap();
 
-    let mut search_queries = self.get_corrected_queries(query, 3);
-    search_queries.insert(0, query.clone());
-    let search_queries_count = search_queries.len();
+    let mut query_builder = QueryBuilder::<Sqlite>::new("SELECT COUNT(*) AS count FROM (");
+    self
+      .backend
+      .push_scored_posts_query(&mut query_builder, &search_queries);
+    push_filtered_search_query(&mut query_builder, filters);
 
-    let query = search_queries
-      .iter()
-      .enumerate()
-      .map(|(index, search_query)| {
-        format!(
-          r#"
-          SELECT model_id, SUM(count * weight * {}) AS score
-          FROM (
-            SELECT id, word, model_type, model_id, appear_in, count(*) AS count,
-            CASE
-              WHEN appear_in = 'post_title' THEN 100
-              WHEN appear_in = 'post_poster_display_name' THEN 50
-              WHEN appear_in = 'post_short_description' THEN 25
-              WHEN appear_in = 'post_tag_name' THEN 5
-              ELSE 1
-            END AS weight
-            FROM word
-            WHERE word In ({})
-            GROUP BY word, model_type, model_id, appear_in
-          ) AS sub
-          GROUP BY model_id
-          ORDER BY score DESC;
-          "#,
-          search_queries_count - index,
-          // @TODO-ZM: Potential SQL injection vulnerability!
-          search_query
-            .split(" ")
-            .map(|word| format!("'{}'", word))
-            .join(", "),
-        )
-      })
-      .collect::<Vec<String>>()
-      .join("\n");
+    let db_result = query_builder.build().fetch_one(&mut *conn).await;
+    if db_result.is_err() {
+      tracing::error!("Error while counting searched posts: {:?}", db_result.err());
+      return Err(SearchError::InternalError);
+    }
+    let total_count = db_result.unwrap().get::<i64, _>("count") as u32;
 
-    let db_results = sqlx::query(&query).fetch_all(&mut *conn).await;
+    let mut query_builder = QueryBuilder::<Sqlite>::new(
+      "SELECT model_id, score, rank FROM (SELECT scored_post.model_id, ",
+    );
+    push_ranking_columns(
+      &mut query_builder,
+      sort,
+      self.recency_half_life_days,
+      ranked_at,
+    );
+    query_builder.push(" FROM (");
+    self
+      .backend
+      .push_scored_posts_query(&mut query_builder, &search_queries);
+    push_filtered_search_query(&mut query_builder, filters);
+    query_builder.push(") AS ranked_post");
 
+    if let Some(cursor) = cursor {
+      query_builder.push(" WHERE rank < ");
+      query_builder.push_bind(cursor.rank);
+      query_builder.push(" OR (rank = ");
+      query_builder.push_bind(cursor.rank);
+      query_builder.push(" AND model_id > ");
+      query_builder.push_bind(cursor.id);
+      query_builder.push(")");
+    }
+
+    query_builder.push(" ORDER BY rank DESC, model_id ASC LIMIT ");
+    query_builder.push_bind(pagination.limit);
+    query_builder.push(" OFFSET ");
+    query_builder.push_bind(match cursor {
+      Some(_) => 0,
+      None => pagination.start,
+    });
+
+    let db_results = query_builder.build().fetch_all(&mut *conn).await;
     if db_results.is_err() {
       tracing::error!("Error while searching posts: {:?}", db_results.err());
       return Err(SearchError::InternalError);
     }
     let db_results = db_results.unwrap();
 
-    let mut model_ids_with_scores = vec![];
+    let records = db_results
+      .iter()
+      .map(|row| SearchRecord {
+        id: row.get::<u32, _>("model_id"),
+        score: row.get::<f64, _>("score"),
+        rank: row.get::<f64, _>("rank"),
+      })
+      .collect::<Vec<SearchRecord>>();
+
+    Ok(SearchResult {
+      records,
+      total_count,
+      corrected_query,
+      search_queries,
+      ranked_at,
+    })
+  }
 
-    for row in db_results {
-      model_ids_with_scores.push((row.get::<u32, _>("model_id"), row.get::<i64, _>("score")));
+  // counted over all the posts matching the search, not only a page of them
+  pub async fn get_post_facets(
+    &self,
+    query: &String,
+    filters: &SearchFilters,
+  ) -> Result<PostFacets, SearchError> {
+    let search_queries = self.get_search_queries(query);
+    if search_queries.is_none() {
+      return Ok(PostFacets::default());
     }
+    let (search_queries, _) = search_queries.unwrap();
 
-    // aggregate the scores for the same model_id
-    let mut model_ids_with_scores = model_ids_with_scores
-      .into_iter()
-      .sorted_by_key(|(model_id, _)| *model_id)
-      .group_by(|(model_id, _)| *model_id)
-      .into_iter()
-      .map(|(model_id, group)| {
-        let mut score = 0;
-        for (_, group_score) in group {
-          score += group_score;
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!(
+        "Error while getting sql connection to get facets: {:?}",
+        conn
+      );
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let mut query_builder = QueryBuilder::<Sqlite>::new(
+      r#"
+      WITH matched_post AS MATERIALIZED (
+        SELECT indexed_post.id, indexed_post.poster_type, indexed_post.wilaya_code, indexed_post.contract_type,
+          (SELECT group_concat(tag_id) FROM indexed_post_tag WHERE post_id = indexed_post.id) AS tag_ids
+        FROM ("#,
+    );
+    self
+      .backend
+      .push_scored_posts_query(&mut query_builder, &search_queries);
+    push_filtered_search_query(&mut query_builder, filters);
+    query_builder.push(")");
+    push_post_facets_query(&mut query_builder);
+
+    let db_result = query_builder.build().fetch_all(&mut *conn).await;
+    if db_result.is_err() {
+      tracing::error!("Error while getting post facets: {:?}", db_result.err());
+      return Err(SearchError::InternalError);
+    }
+
+    Ok(get_post_facets_from_rows(&db_result.unwrap()))
+  }
+
+  pub async fn search_accounts(
+    &self,
+    query: &String,
+    pagination: &DBPaginationQuery,
+  ) -> Result<SearchResult, SearchError> {
+    // accounts don't age, their rank is their score
+    let ranked_at = get_current_julian_day();
+
+    let search_queries = self.get_search_queries(query);
+    if search_queries.is_none() {
+      return Ok(SearchResult {
+        records: vec![],
+        total_count: 0,
+        corrected_query: None,
+        search_queries: vec![],
+        ranked_at,
+      });
+    }
+    let (search_queries, corrected_query) = search_queries.unwrap();
+
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection to search: {:?}", conn);
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let mut query_builder = QueryBuilder::<Sqlite>::new("SELECT COUNT(*) AS count FROM (");
+    self
+      .backend
+      .push_scored_accounts_query(&mut query_builder, &search_queries);
+    query_builder.push(") AS scored_account");
+
+    let db_result = query_builder.build().fetch_one(&mut *conn).await;
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while counting searched accounts: {:?}",
+        db_result.err()
+      );
+      return Err(SearchError::InternalError);
+    }
+    let total_count = db_result.unwrap().get::<i64, _>("count") as u32;
+
+    let mut query_builder = QueryBuilder::<Sqlite>::new("SELECT model_id, score FROM (");
+    self
+      .backend
+      .push_scored_accounts_query(&mut query_builder, &search_queries);
+    query_builder.push(") AS scored_account ORDER BY score DESC, model_id ASC LIMIT ");
+    query_builder.push_bind(pagination.limit);
+    query_builder.push(" OFFSET ");
+    query_builder.push_bind(pagination.start);
+
+    let db_results = query_builder.build().fetch_all(&mut *conn).await;
+    if db_results.is_err() {
+      tracing::error!("Error while searching accounts: {:?}", db_results.err());
+      return Err(SearchError::InternalError);
+    }
+    let db_results = db_results.unwrap();
+
+    let records = db_results
+      .iter()
+      .map(|row| {
+        let score = row.get::<f64, _>("score");
+        SearchRecord {
+          id: row.get::<u32, _>("model_id"),
+          score,
+          rank: score,
         }
-        (model_id, score)
       })
-      .collect::<Vec<(u32, i64)>>();
+      .collect::<Vec<SearchRecord>>();
+
+    Ok(SearchResult {
+      records,
+      total_count,
+      corrected_query,
+      search_queries,
+      ranked_at,
+    })
+  }
+
+  fn push_scored_posts_and_accounts_query(
+    &self,
+    query_builder: &mut QueryBuilder<'_, Sqlite>,
+    search_queries: &Vec<ParsedSearchQuery>,
+  ) {
+    query_builder.push("SELECT 'post' AS model_type, scored_post.model_id, score FROM (");
+    self
+      .backend
+      .push_scored_posts_query(query_builder, search_queries);
+    push_filtered_search_query(query_builder, &SearchFilters::default());
+    query_builder.push(" UNION ALL SELECT 'account' AS model_type, model_id, score FROM (");
+    self
+      .backend
+      .push_scored_accounts_query(query_builder, search_queries);
+    query_builder.push(") AS scored_account");
+  }
+
+  pub async fn search_posts_and_accounts(
+    &self,
+    query: &String,
+    pagination: &DBPaginationQuery,
+  ) -> Result<TypedSearchResult, SearchError> {
+    let search_queries = self.get_search_queries(query);
+    if search_queries.is_none() {
+      return Ok(TypedSearchResult {
+        records: vec![],
+        total_count: 0,
+        corrected_query: None,
+        search_queries: vec![],
+      });
+    }
+    let (search_queries, corrected_query) = search_queries.unwrap();
+
+    let conn = self.search_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection to search: {:?}", conn);
+      return Err(SearchError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let mut query_builder = QueryBuilder::<Sqlite>::new("SELECT COUNT(*) AS count FROM (");
+    self.push_scored_posts_and_accounts_query(&mut query_builder, &search_queries);
+    query_builder.push(") AS scored_model");
+
+    let db_result = query_builder.build().fetch_one(&mut *conn).await;
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while counting searched models: {:?}",
+        db_result.err()
+      );
+      return Err(SearchError::InternalError);
+    }
+    let total_count = db_result.unwrap().get::<i64, _>("count") as u32;
+
+    let mut query_builder =
+      QueryBuilder::<Sqlite>::new("SELECT model_type, model_id, score FROM (");
+    self.push_scored_posts_and_accounts_query(&mut query_builder, &search_queries);
+    query_builder
+      .push(") AS scored_model ORDER BY score DESC, model_type ASC, model_id ASC LIMIT ");
+    query_builder.push_bind(pagination.limit);
+    query_builder.push(" OFFSET ");
+    query_builder.push_bind(pagination.start);
+
+    let db_results = query_builder.build().fetch_all(&mut *conn).await;
+    if db_results.is_err() {
+      tracing::error!("Error while searching models: {:?}", db_results.err());
+      return Err(SearchError::InternalError);
+    }
+    let db_results = db_results.unwrap();
+
+    let records = db_results
+      .iter()
+      .map(|row| TypedSearchRecord {
+        model_type: match row.get::<String, _>("model_type").as_str() {
+          "account" => SearchModelType::Account,
+          _ => SearchModelType::Post,
+        },
+        id: row.get::<u32, _>("model_id"),
+        score: row.get::<f64, _>("score"),
+      })
+      .collect::<Vec<TypedSearchRecord>>();
+
+    Ok(TypedSearchResult {
+      records,
+      total_count,
+      corrected_query,
+      search_queries,
+    })
+  }
+}
 
-    model_ids_with_scores.sort_by_key(|(_, score)| -score);
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+  use sqlx::sqlite::SqlitePoolOptions;
 
-    let model_ids_sorted = model_ids_with_scores
+  use super::*;
+  use crate::{
+    config::service::StemmingAlgorithm,
+    search::model::{parse_search_cursor, SearchRecordTrait},
+  };
+
+  const HOSTILE_QUERIES: [&str; 8] = [
+    "'; DROP TABLE indexed_post; --",
+    "' OR 1=1 --",
+    "\" OR \"\"=\"",
+    "\"unbalanced phrase",
+    "a\"b\"c",
+    ";;; --- ''' \"\" ***",
+    "NEAR(react developpeur) OR * AND ^react",
+    "🦀 🚀 \u{0} \u{202e}",
+  ];
+
+  // a single connection, as each one would otherwise open its own empty in-memory database
+  async fn create_search_service(backend_name: SearchBackendName) -> SearchService {
+    let search_sql_db = SqlitePoolOptions::new()
+      .max_connections(1)
+      .connect("sqlite::memory:")
+      .await
+      .unwrap();
+    sqlx::migrate!("./db/search/migrations")
+      .run(&search_sql_db)
+      .await
+      .unwrap();
+    let search_sql_db = Arc::new(search_sql_db);
+
+    let stemmer = Stemmer {
+      english: StemmingAlgorithm::Light,
+      french: StemmingAlgorithm::Light,
+    };
+    let backend: Box<dyn SearchBackend> = match backend_name {
+      SearchBackendName::Word => Box::new(WordSearchBackend::new(
+        Arc::clone(&search_sql_db),
+        stemmer.clone(),
+      )),
+      SearchBackendName::Fts => Box::new(FTSSearchBackend::new(
+        Arc::clone(&search_sql_db),
+        stemmer.clone(),
+      )),
+    };
+
+    let search_service = SearchService {
+      search_sql_db,
+      backend,
+      stemmer,
+      recency_half_life_days: 30.0,
+      correction_index: Arc::new(Mutex::new(CorrectionIndex::new())),
+      suggestion_trie: Arc::new(Mutex::new(SuggestionTrie::new())),
+    };
+
+    let posts = vec![
+      json!({
+        "id": 1,
+        "slug": "developpeur-react",
+        "title": "Développeur React",
+        "poster_id": 1,
+        "short_description": "Développeur React à Alger",
+        "description": "Nous cherchons un **développeur** React",
+        "tag_ids": [1],
+        "published_at": "2024-01-01T00:00:00.000Z",
+        "is_published": true,
+      }),
+      json!({
+        "id": 2,
+        "slug": "web-developer",
+        "title": "مطور ويب",
+        "poster_id": 1,
+        "short_description": "مطور ويب في وهران",
+        "description": "نبحث عن مطور ويب",
+        "tag_ids": [1],
+        "published_at": "2024-01-02T00:00:00.000Z",
+        "is_published": true,
+      }),
+    ]
+    .into_iter()
+    .map(|post| serde_json::from_value::<Post>(post).unwrap())
+    .collect::<Vec<Post>>();
+    let tags = vec![CompactTag {
+      id: 1,
+      slug: "react".to_string(),
+      name: "React".to_string(),
+    }];
+    let posters = vec![serde_json::from_value::<CompactAccount>(json!({
+      "id": 1,
+      "slug": "acme",
+      "type": "Company",
+      "company_name": "Acme",
+    }))
+    .unwrap()];
+
+    search_service
+      .index_posts(posts, tags, posters.clone())
+      .await
+      .unwrap();
+    search_service.index_accounts(posters).await.unwrap();
+
+    search_service
+  }
+
+  fn get_pagination() -> DBPaginationQuery {
+    DBPaginationQuery {
+      limit: 10,
+      start: 0,
+    }
+  }
+
+  async fn search_post_ids(search_service: &SearchService, query: &str) -> Vec<u32> {
+    search_service
+      .search_posts(
+        &query.to_string(),
+        &SearchFilters::default(),
+        &SearchSort::default(),
+        &None,
+        &get_pagination(),
+      )
+      .await
+      .unwrap()
+      .records
       .iter()
-      .map(|(model_id, _)| *model_id)
-      .collect::<Vec<u32>>();
+      .map(|record| record.id)
+      .collect()
+  }
+
+  async fn assert_hostile_queries_are_inert(backend_name: SearchBackendName) {
+    let search_service = create_search_service(backend_name).await;
+    let long_word = "a".repeat(100_000);
+    let many_words = vec!["react"; 1000].join(" ");
+
+    let mut queries = HOSTILE_QUERIES
+      .iter()
+      .map(|query| query.to_string())
+      .collect::<Vec<String>>();
+    queries.push(long_word);
+    queries.push(many_words);
+
+    for query in &queries {
+      for sort in [SearchSort::Relevance, SearchSort::Date, SearchSort::Blend] {
+        for cursor in [
+          None,
+          Some(SearchCursor {
+            rank: 1.0,
+            id: 1,
+            ranked_at: get_current_julian_day(),
+          }),
+        ] {
+          let result = search_service
+            .search_posts(
+              query,
+              &SearchFilters::default(),
+              &sort,
+              &cursor,
+              &get_pagination(),
+            )
+            .await;
+          assert!(result.is_ok(), "search_posts failed for: {:?}", query);
+        }
+      }
+
+      let facets = search_service
+        .get_post_facets(query, &SearchFilters::default())
+        .await;
+      assert!(facets.is_ok(), "get_post_facets failed for: {:?}", query);
+
+      let accounts = search_service
+        .search_accounts(query, &get_pagination())
+        .await;
+      assert!(accounts.is_ok(), "search_accounts failed for: {:?}", query);
+
+      let models = search_service
+        .search_posts_and_accounts(query, &get_pagination())
+        .await;
+      assert!(
+        models.is_ok(),
+        "search_posts_and_accounts failed for: {:?}",
+        query
+      );
+    }
+
+    for query in &HOSTILE_QUERIES[..3] {
+      assert!(search_post_ids(&search_service, query).await.is_empty());
+    }
+    assert_eq!(search_service.get_indexed_post_count().await.unwrap(), 2);
+  }
+
+  async fn assert_empty_queries_match_nothing(backend_name: SearchBackendName) {
+    let search_service = create_search_service(backend_name).await;
+
+    for query in ["", "   ", "\n\t", "\"\"", "-- ;"] {
+      let result = search_service
+        .search_posts(
+          &query.to_string(),
+          &SearchFilters::default(),
+          &SearchSort::default(),
+          &None,
+          &get_pagination(),
+        )
+        .await
+        .unwrap();
+      assert_eq!(result.total_count, 0);
+      assert!(result.records.is_empty());
+    }
+  }
+
+  async fn assert_unicode_queries_match(backend_name: SearchBackendName) {
+    let search_service = create_search_service(backend_name).await;
+
+    assert!(search_post_ids(&search_service, "developpeur")
+      .await
+      .contains(&1));
+    assert!(search_post_ids(&search_service, "DÉVELOPPEUR")
+      .await
+      .contains(&1));
+    assert!(search_post_ids(&search_service, "مطور").await.contains(&2));
+    assert!(search_post_ids(&search_service, "مطوّر").await.contains(&2));
+  }
+
+  // the first page of a search is ranked in the past, as if the next page was requested a year later
+  async fn assert_blend_cursor_resumes_where_the_page_ended(backend_name: SearchBackendName) {
+    let search_service = create_search_service(backend_name).await;
+    let query = "acme".to_string();
+    let pagination = DBPaginationQuery { limit: 1, start: 0 };
+
+    let mut cursor = Some(SearchCursor {
+      rank: f64::MAX,
+      id: 0,
+      ranked_at: get_current_julian_day() - 365.0,
+    });
+    let mut post_ids = vec![];
+    for _ in 0..3 {
+      let result = search_service
+        .search_posts(
+          &query,
+          &SearchFilters::default(),
+          &SearchSort::Blend,
+          &cursor,
+          &pagination,
+        )
+        .await
+        .unwrap();
+      post_ids.extend(result.records.iter().map(|record| record.id));
+      cursor = result
+        .records
+        .last()
+        .map(|record| parse_search_cursor(&record.to_cursor(result.ranked_at)).unwrap());
+    }
+
+    post_ids.sort();
+    assert_eq!(post_ids, vec![1, 2]);
+  }
+
+  async fn assert_corrections_are_shown_as_indexed_words(backend_name: SearchBackendName) {
+    let search_service = create_search_service(backend_name).await;
+    search_service.refresh_bk_tree().await.unwrap();
+
+    let correction_index = search_service.correction_index.lock().unwrap();
+    let stem = search_service.stemmer.stem(&"developpeur".to_string());
+    assert_ne!(stem, "developpeur");
+    assert_eq!(
+      correction_index
+        .display_words
+        .get(&stem)
+        .map(String::as_str),
+      Some("developpeur")
+    );
+  }
+
+  async fn assert_unindexed_accounts_are_not_found(backend_name: SearchBackendName) {
+    let search_service = create_search_service(backend_name).await;
+    let query = "acme".to_string();
+
+    let result = search_service
+      .search_accounts(&query, &get_pagination())
+      .await
+      .unwrap();
+    assert_eq!(result.total_count, 1);
+
+    search_service.unindex_accounts(vec![1]).await.unwrap();
+    let result = search_service
+      .search_accounts(&query, &get_pagination())
+      .await
+      .unwrap();
+    assert_eq!(result.total_count, 0);
+  }
+
+  async fn assert_reindexed_posts_lose_their_old_words(backend_name: SearchBackendName) {
+    let search_service = create_search_service(backend_name).await;
+    assert!(search_post_ids(&search_service, "react").await.contains(&1));
+
+    let post = serde_json::from_value::<Post>(json!({
+      "id": 1,
+      "slug": "developpeur-vue",
+      "title": "Développeur Vue",
+      "poster_id": 1,
+      "short_description": "Développeur Vue à Alger",
+      "description": "Nous cherchons un développeur Vue",
+      "tag_ids": [],
+      "published_at": "2024-01-01T00:00:00.000Z",
+      "is_published": true,
+    }))
+    .unwrap();
+    let posters = vec![serde_json::from_value::<CompactAccount>(json!({
+      "id": 1,
+      "slug": "acme",
+      "type": "Company",
+      "company_name": "Acme",
+    }))
+    .unwrap()];
+    search_service
+      .index_posts(vec![post], vec![], posters)
+      .await
+      .unwrap();
+
+    assert!(!search_post_ids(&search_service, "react").await.contains(&1));
+    assert!(search_post_ids(&search_service, "vue").await.contains(&1));
+  }
+
+  #[tokio::test]
+  async fn word_backend_hostile_queries_are_inert() {
+    assert_hostile_queries_are_inert(SearchBackendName::Word).await;
+  }
+
+  #[tokio::test]
+  async fn fts_backend_hostile_queries_are_inert() {
+    assert_hostile_queries_are_inert(SearchBackendName::Fts).await;
+  }
+
+  #[tokio::test]
+  async fn word_backend_empty_queries_match_nothing() {
+    assert_empty_queries_match_nothing(SearchBackendName::Word).await;
+  }
+
+  #[tokio::test]
+  async fn fts_backend_empty_queries_match_nothing() {
+    assert_empty_queries_match_nothing(SearchBackendName::Fts).await;
+  }
+
+  #[tokio::test]
+  async fn word_backend_unicode_queries_match() {
+    assert_unicode_queries_match(SearchBackendName::Word).await;
+  }
+
+  #[tokio::test]
+  async fn fts_backend_unicode_queries_match() {
+    assert_unicode_queries_match(SearchBackendName::Fts).await;
+  }
+
+  #[tokio::test]
+  async fn word_backend_blend_cursor_resumes_where_the_page_ended() {
+    assert_blend_cursor_resumes_where_the_page_ended(SearchBackendName::Word).await;
+  }
+
+  #[tokio::test]
+  async fn fts_backend_blend_cursor_resumes_where_the_page_ended() {
+    assert_blend_cursor_resumes_where_the_page_ended(SearchBackendName::Fts).await;
+  }
+
+  #[tokio::test]
+  async fn word_backend_corrections_are_shown_as_indexed_words() {
+    assert_corrections_are_shown_as_indexed_words(SearchBackendName::Word).await;
+  }
+
+  #[tokio::test]
+  async fn fts_backend_corrections_are_shown_as_indexed_words() {
+    assert_corrections_are_shown_as_indexed_words(SearchBackendName::Fts).await;
+  }
+
+  #[tokio::test]
+  async fn word_backend_unindexed_accounts_are_not_found() {
+    assert_unindexed_accounts_are_not_found(SearchBackendName::Word).await;
+  }
+
+  #[tokio::test]
+  async fn fts_backend_unindexed_accounts_are_not_found() {
+    assert_unindexed_accounts_are_not_found(SearchBackendName::Fts).await;
+  }
+
+  #[tokio::test]
+  async fn word_backend_reindexed_posts_lose_their_old_words() {
+    assert_reindexed_posts_lose_their_old_words(SearchBackendName::Word).await;
+  }
 
-    Ok(model_ids_sorted)
+  #[tokio::test]
+  async fn fts_backend_reindexed_posts_lose_their_old_words() {
+    assert_reindexed_posts_lose_their_old_words(SearchBackendName::Fts).await;
   }
 }
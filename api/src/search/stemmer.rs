@@ -0,0 +1,132 @@
+use crate::config::service::StemmingAlgorithm;
+
+// expects words normalized by normalize_text, so french accents are already gone
+fn is_stemmable(word: &str) -> bool {
+  word.chars().all(|c| c.is_ascii_lowercase())
+}
+
+fn has_vowel(s: &str) -> bool {
+  s.chars()
+    .any(|c| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y'))
+}
+
+// harman's s-stemmer, eg: "companies" -> "company", "developers" -> "developer"
+fn stem_english_plural(word: &str) -> String {
+  let length = word.len();
+
+  if length > 4 && word.ends_with("ies") && !word.ends_with("eies") && !word.ends_with("aies") {
+    return format!("{}y", &word[..length - 3]);
+  }
+  if length > 3
+    && word.ends_with("es")
+    && !word.ends_with("aes")
+    && !word.ends_with("ees")
+    && !word.ends_with("oes")
+  {
+    return word[..length - 1].to_string();
+  }
+  if length > 3 && word.ends_with('s') && !word.ends_with("us") && !word.ends_with("ss") {
+    return word[..length - 1].to_string();
+  }
+
+  word.to_string()
+}
+
+// plurals plus "-ing" and "-ed", eg: "managing" -> "manag", "skilled" -> "skill"
+fn stem_english_light(word: &str) -> String {
+  let word = stem_english_plural(word);
+  let length = word.len();
+
+  if word.ends_with("ing") && length > 5 && has_vowel(&word[..length - 3]) {
+    return word[..length - 3].to_string();
+  }
+  if word.ends_with("ed") && !word.ends_with("eed") && length > 4 && has_vowel(&word[..length - 2])
+  {
+    return word[..length - 2].to_string();
+  }
+
+  word
+}
+
+// eg: "commerciaux" -> "commercial", "ingenieurs" -> "ingenieur"
+fn stem_french_plural(word: &str) -> String {
+  let length = word.len();
+
+  if length > 4 && word.ends_with("aux") {
+    return format!("{}al", &word[..length - 3]);
+  }
+  if length > 3 && (word.ends_with('s') || word.ends_with('x')) && !word.ends_with("ss") {
+    return word[..length - 1].to_string();
+  }
+
+  word.to_string()
+}
+
+// savoy's light stemmer with the common feminine endings,
+// eg: "developpeuses" -> "developpeu", "developpeur" -> "developpeu"
+fn stem_french_light(word: &str) -> String {
+  let mut word = stem_french_plural(word);
+
+  for (feminine, masculine) in [
+    ("trice", "teur"),
+    ("euse", "eur"),
+    ("ienne", "ien"),
+    ("ive", "if"),
+  ] {
+    if word.len() > feminine.len() + 2 && word.ends_with(feminine) {
+      word = format!("{}{}", &word[..word.len() - feminine.len()], masculine);
+      break;
+    }
+  }
+
+  // "ingenieure" and "ingenieur" both end up as "ingenieu"
+  for ending in ['e', 'r', 'e'] {
+    if word.len() > 5 && word.ends_with(ending) {
+      word.pop();
+    }
+  }
+
+  let mut chars = word.chars().rev();
+  if word.len() > 5 && chars.next() == chars.next() {
+    word.pop();
+  }
+
+  word
+}
+
+fn stem_with(
+  algorithm: &StemmingAlgorithm,
+  word: String,
+  plural: fn(&str) -> String,
+  light: fn(&str) -> String,
+) -> String {
+  match algorithm {
+    StemmingAlgorithm::None => word,
+    StemmingAlgorithm::Plural => plural(&word),
+    StemmingAlgorithm::Light => light(&word),
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct Stemmer {
+  pub english: StemmingAlgorithm,
+  pub french: StemmingAlgorithm,
+}
+
+impl Stemmer {
+  // a word's language is unknown, so it goes through the english then the french algorithm,
+  // which reduces words of both languages to the same stem as their variants
+  pub fn stem(&self, word: &str) -> String {
+    if !is_stemmable(word) {
+      return word.to_string();
+    }
+
+    let word = stem_with(
+      &self.english,
+      word.to_string(),
+      stem_english_plural,
+      stem_english_light,
+    );
+    stem_with(&self.french, word, stem_french_plural, stem_french_light)
+  }
+}
@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use super::model::Suggestion;
+
+pub const MAX_SUGGESTIONS_PER_PREFIX: usize = 10;
+
+#[derive(Default)]
+struct TrieNode {
+  children: HashMap<char, TrieNode>,
+  // the best suggestions of the whole sub-tree, so lookups don't have to walk it
+  top_suggestions: Vec<Suggestion>,
+}
+
+#[derive(Default)]
+pub struct SuggestionTrie {
+  root: TrieNode,
+}
+
+impl SuggestionTrie {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn insert(&mut self, key: &str, suggestion: Suggestion) {
+    let mut node = &mut self.root;
+    for c in key.chars() {
+      node = node.children.entry(c).or_default();
+
+      let existing_suggestion = node.top_suggestions.iter_mut().find(|top_suggestion| {
+        top_suggestion.text == suggestion.text && top_suggestion.r#type == suggestion.r#type
+      });
+      match existing_suggestion {
+        Some(existing_suggestion) => {
+          existing_suggestion.score = existing_suggestion.score.max(suggestion.score)
+        }
+        None => node.top_suggestions.push(suggestion.clone()),
+      }
+
+      node
+        .top_suggestions
+        .sort_by(|a, b| b.score.cmp(&a.score).then(a.text.cmp(&b.text)));
+      node.top_suggestions.truncate(MAX_SUGGESTIONS_PER_PREFIX);
+    }
+  }
+
+  pub fn find(&self, prefix: &str) -> Vec<Suggestion> {
+    let mut node = &self.root;
+    for c in prefix.chars() {
+      match node.children.get(&c) {
+        Some(child) => node = child,
+        None => return vec![],
+      }
+    }
+
+    node.top_suggestions.clone()
+  }
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SpamRuleKind {
+  // eg: "URGENT RECRUTEMENT IMMEDIAT"
+  AllCapsTitle {
+    min_letter_count: usize,
+    min_uppercase_ratio: f64,
+  },
+  // algerian mobile and landline numbers, eg: "0555 12 34 56", "+213 21 12 34 56"
+  PhoneNumber,
+  // links in the description pointing to one of the domains, or one of their subdomains
+  LinkDomain {
+    domains: Vec<String>,
+  },
+  // words or phrases found in the title or the description, compared after normalize_text
+  Keyword {
+    keywords: Vec<String>,
+  },
+  PosterEmailDomain {
+    domains: Vec<String>,
+  },
+  ShortDescription {
+    min_word_count: usize,
+  },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SpamRule {
+  pub name: String,
+  pub weight: f64,
+  // shown to admins, and to posters when their post is rejected
+  pub explanation: String,
+  #[serde(flatten)]
+  pub kind: SpamRuleKind,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SpamRules {
+  pub moderate_threshold: f64,
+  pub reject_threshold: f64,
+  pub rules: Vec<SpamRule>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SpamRuleMatch {
+  pub name: String,
+  pub weight: f64,
+  pub explanation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SpamVerdict {
+  Allow,
+  Moderate,
+  Reject,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SpamScore {
+  pub score: f64,
+  pub matched_rules: Vec<SpamRuleMatch>,
+  pub verdict: SpamVerdict,
+}
+
+// what's kept of the score once the post is created, the verdict depends on the thresholds of the day
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PostSpamScore {
+  pub post_id: u32,
+  pub score: f64,
+  pub matched_rules: Vec<SpamRuleMatch>,
+}
@@ -0,0 +1,215 @@
+use std::sync::Arc;
+
+use super::model::{SpamRuleKind, SpamRuleMatch, SpamRules, SpamScore, SpamVerdict};
+use crate::{
+  _utils::{
+    error::BootError,
+    string::{get_words, normalize_text},
+  },
+  config::service::ConfigService,
+};
+
+// shipped with the binary, so the api boots without the file next to it
+const DEFAULT_SPAM_RULES: &str = include_str!("../../spam_rules.json");
+
+// space padded so keywords only match whole words, eg: " frais de dossier "
+fn get_padded_words(s: &str) -> String {
+  format!(
+    " {} ",
+    get_words(&normalize_text(s))
+      .collect::<Vec<&str>>()
+      .join(" ")
+  )
+}
+
+// digits separated by at most one space, dot or dash are read as one number, eg: "0555 12.34-56"
+fn get_numbers(s: &str) -> Vec<String> {
+  let mut numbers = vec![];
+  let mut number = String::new();
+  let mut has_separator = false;
+
+  for c in normalize_text(s).chars() {
+    if c.is_ascii_digit() {
+      number.push(c);
+      has_separator = false;
+      continue;
+    }
+
+    let has_digits = number.chars().any(|c| c.is_ascii_digit());
+    if has_digits && !has_separator && matches!(c, ' ' | '.' | '-') {
+      has_separator = true;
+      continue;
+    }
+
+    if has_digits {
+      numbers.push(number.clone());
+    }
+    number.clear();
+    has_separator = false;
+    if c == '+' {
+      number.push(c);
+    }
+  }
+  if number.chars().any(|c| c.is_ascii_digit()) {
+    numbers.push(number);
+  }
+
+  numbers
+}
+
+fn is_phone_number(number: &str) -> bool {
+  let local_number = match number.strip_prefix("+213").or(number.strip_prefix("00213")) {
+    Some(rest) => format!("0{}", rest),
+    None => number.to_string(),
+  };
+  let digits = local_number.as_bytes();
+
+  // mobile numbers are 05, 06 or 07 followed by 8 digits, landlines are 02, 03 or 04 followed by 7
+  matches!(
+    (digits.len(), digits.first(), digits.get(1)),
+    (10, Some(b'0'), Some(b'5'..=b'7')) | (9, Some(b'0'), Some(b'2'..=b'4'))
+  )
+}
+
+// markdown links are split apart from their text, eg: "[apply](https://bit.ly/abc)"
+fn get_link_hosts(s: &str) -> Vec<String> {
+  s.split(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | '[' | ']' | '<' | '>' | '"'))
+    .filter(|word| !word.is_empty())
+    .filter_map(|word| {
+      let word = word
+        .to_lowercase()
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_string();
+      let word = word
+        .strip_prefix("https://")
+        .or(word.strip_prefix("http://"))
+        .unwrap_or(&word);
+      let word = word.strip_prefix("www.").unwrap_or(word);
+      let host = word.split(['/', '?', '#', ':']).next().unwrap_or("");
+
+      match host.contains('.') {
+        true => Some(host.to_string()),
+        false => None,
+      }
+    })
+    .collect()
+}
+
+fn is_domain_or_subdomain(host: &str, domains: &Vec<String>) -> bool {
+  domains.iter().any(|domain| {
+    let domain = domain.to_lowercase();
+    host == domain || host.ends_with(&format!(".{}", domain))
+  })
+}
+
+fn is_all_caps(s: &str, min_letter_count: usize, min_uppercase_ratio: f64) -> bool {
+  // arabic letters have no case, they're left out of the count
+  let cased_letters = s
+    .chars()
+    .filter(|c| c.is_uppercase() || c.is_lowercase())
+    .collect::<Vec<char>>();
+  if cased_letters.len() < min_letter_count {
+    return false;
+  }
+
+  let uppercase_count = cased_letters.iter().filter(|c| c.is_uppercase()).count();
+  uppercase_count as f64 / cased_letters.len() as f64 >= min_uppercase_ratio
+}
+
+pub struct SpamService {
+  rules: SpamRules,
+}
+
+impl SpamService {
+  pub fn new(config_service: Arc<ConfigService>) -> Result<Self, BootError> {
+    let content = match config_service.get_config().spam_rules_path {
+      Some(path) => {
+        tracing::info!("Loading the spam rules from: {}", path);
+
+        let content = std::fs::read_to_string(&path);
+        if content.is_err() {
+          tracing::error!("Failed to read the spam rules: {}", content.err().unwrap());
+          return Err(BootError::SpamRulesSetupError);
+        }
+        content.unwrap()
+      }
+      None => DEFAULT_SPAM_RULES.to_string(),
+    };
+
+    let rules = serde_json::from_str::<SpamRules>(&content);
+    if rules.is_err() {
+      tracing::error!("Failed to parse the spam rules: {}", rules.err().unwrap());
+      return Err(BootError::SpamRulesSetupError);
+    }
+    let rules = rules.unwrap();
+
+    if !(rules.moderate_threshold > 0.0 && rules.moderate_threshold <= rules.reject_threshold)
+      || rules.rules.iter().any(|rule| !rule.weight.is_finite())
+    {
+      tracing::error!("Invalid spam rules, check the thresholds and weights");
+      return Err(BootError::SpamRulesSetupError);
+    }
+
+    Ok(Self { rules })
+  }
+
+  pub fn get_post_spam_score(
+    &self,
+    title: &String,
+    description: &String,
+    poster_email: &String,
+  ) -> SpamScore {
+    let padded_words = get_padded_words(&format!("{}\n{}", title, description));
+    let link_hosts = get_link_hosts(description);
+    let poster_email_domain = poster_email
+      .rsplit_once('@')
+      .map(|(_, domain)| domain.trim().to_lowercase())
+      .unwrap_or_default();
+
+    let matched_rules = self
+      .rules
+      .rules
+      .iter()
+      .filter(|rule| match &rule.kind {
+        SpamRuleKind::AllCapsTitle {
+          min_letter_count,
+          min_uppercase_ratio,
+        } => is_all_caps(title, *min_letter_count, *min_uppercase_ratio),
+        SpamRuleKind::PhoneNumber => get_numbers(description)
+          .iter()
+          .any(|number| is_phone_number(number)),
+        SpamRuleKind::LinkDomain { domains } => link_hosts
+          .iter()
+          .any(|host| is_domain_or_subdomain(host, domains)),
+        SpamRuleKind::Keyword { keywords } => keywords.iter().any(|keyword| {
+          let padded_keyword = get_padded_words(keyword);
+          !padded_keyword.trim().is_empty() && padded_words.contains(&padded_keyword)
+        }),
+        SpamRuleKind::PosterEmailDomain { domains } => {
+          is_domain_or_subdomain(&poster_email_domain, domains)
+        }
+        SpamRuleKind::ShortDescription { min_word_count } => {
+          get_words(description).count() < *min_word_count
+        }
+      })
+      .map(|rule| SpamRuleMatch {
+        name: rule.name.clone(),
+        weight: rule.weight,
+        explanation: rule.explanation.clone(),
+      })
+      .collect::<Vec<SpamRuleMatch>>();
+
+    let score = matched_rules.iter().map(|rule| rule.weight).sum::<f64>();
+    let verdict = match score {
+      score if score >= self.rules.reject_threshold => SpamVerdict::Reject,
+      score if score >= self.rules.moderate_threshold => SpamVerdict::Moderate,
+      _ => SpamVerdict::Allow,
+    };
+
+    SpamScore {
+      score,
+      matched_rules,
+      verdict,
+    }
+  }
+}
@@ -1,5 +1,5 @@
 use serde_json::json;
-use sqlx::{Pool, Row, Sqlite};
+use sqlx::{Pool, QueryBuilder, Row, Sqlite};
 use std::sync::Arc;
 
 use super::model::{CompactTag, DBTag, Tag};
@@ -122,6 +122,55 @@ impl TagRepository {
     Ok(compact_tags)
   }
 
+  pub async fn get_many_compact_tags_by_slugs(
+    &self,
+    slugs: &Vec<String>,
+  ) -> Result<Vec<CompactTag>, DataAccessError> {
+    if slugs.is_empty() {
+      return Ok(vec![]);
+    }
+
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let mut query_builder =
+      QueryBuilder::<Sqlite>::new("SELECT id, name, slug FROM tag WHERE slug IN (");
+    let mut separated = query_builder.separated(", ");
+    for slug in slugs {
+      separated.push_bind(slug.clone());
+    }
+    separated.push_unseparated(")");
+
+    let result = query_builder.build().fetch_all(&mut *conn).await;
+
+    if result.is_err() {
+      tracing::error!(
+        "Error while getting many compact tags by slugs: {:?}",
+        result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+    let result = result.unwrap();
+
+    let mut compact_tags = vec![];
+
+    for row in result {
+      let json_tag = json!({
+        "id": row.get::<u32, _>("id"),
+        "name": row.get::<String, _>("name"),
+        "slug": row.get::<String, _>("slug"),
+      });
+      let compact_tag: CompactTag = serde_json::from_value(json_tag).unwrap();
+      compact_tags.push(compact_tag);
+    }
+
+    Ok(compact_tags)
+  }
+
   pub async fn get_one_tag_by_slug(&self, slug: &str) -> Result<Tag, DataAccessError> {
     let conn = self.main_sql_db.acquire().await;
     if conn.is_err() {
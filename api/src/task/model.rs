@@ -23,6 +23,8 @@ pub enum TaskType {
 pub enum TaskName {
   Indexing { model_name: String, model_id: u32 },
   UndoIndexing { model_name: String, model_id: u32 },
+  // waits for the model's publishing date
+  Publishing { model_name: String, model_id: u32 },
   RefreshingBKTree,
 }
 
@@ -56,6 +58,10 @@ impl DBTaskTrait for DBTask {
       | TaskName::UndoIndexing {
         model_name,
         model_id,
+      }
+      | TaskName::Publishing {
+        model_name,
+        model_id,
       } => (Some(model_name.clone()), Some(*model_id)),
       _ => (None, None),
     }
@@ -1,5 +1,5 @@
 use serde_json::json;
-use sqlx::{Pool, Row, Sqlite};
+use sqlx::{sqlite::SqliteRow, Pool, Row, Sqlite};
 use std::sync::Arc;
 
 use super::model::{DBTask, DBTaskTrait};
@@ -8,6 +8,29 @@ use crate::{
   task::model::Task,
 };
 
+fn get_task_from_row(row: &SqliteRow) -> Result<Task, DataAccessError> {
+  let json_task = json!({
+    "id": row.get::<u32, _>("id"),
+    "name": row.get::<String, _>("name"),
+    "model_name": row.get::<String, _>("model_name"),
+    "model_id": row.get::<u32, _>("model_id"),
+    "type": row.get::<String, _>("type"),
+    "manual_task_owner": row.get::<Option<String>, _>("manual_task_owner"),
+    "status": row.get::<String, _>("status"),
+    "failure_reason": row.get::<Option<String>, _>("failure_reason"),
+    "created_at": row.get::<String, _>("created_at"),
+    "updated_at": row.get::<String, _>("updated_at"),
+  });
+
+  let task = serde_json::from_value::<Task>(json_task);
+  if task.is_err() {
+    tracing::error!("Error while deserializing task: {:?}", task);
+    return Err(DataAccessError::InternalError);
+  }
+
+  Ok(task.unwrap())
+}
+
 pub struct TaskRepository {
   main_sql_db: Arc<Pool<Sqlite>>,
 }
@@ -63,29 +86,53 @@ impl TaskRepository {
     let db_result = db_result.unwrap();
 
     let mut tasks = Vec::new();
+    for row in db_result {
+      tasks.push(get_task_from_row(&row)?);
+    }
+
+    Ok(tasks)
+  }
+
+  // the publishing tasks of posts whose publishing date is reached, the earliest first,
+  // tasks without a post are due right away, so they get completed
+  pub async fn get_many_due_publishing_tasks(
+    &self,
+    limit: u32,
+  ) -> Result<Vec<Task>, DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      r#"
+      SELECT task.*
+      FROM task
+      LEFT JOIN post ON post.id = task.model_id
+      WHERE task.status = 'Pending' AND task.name = 'Publishing' AND task.model_name = 'post'
+        AND (post.id IS NULL OR substr(post.published_at, 1, 19) <= strftime('%Y-%m-%dT%H:%M:%S', 'now'))
+      ORDER BY post.published_at ASC, task.id ASC
+      LIMIT $1
+      "#,
+    )
+    .bind(limit)
+    .fetch_all(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!(
+        "Error while getting many due publishing tasks: {:?}",
+        db_result.err()
+      );
+      return Err(DataAccessError::InternalError);
+    }
+    let db_result = db_result.unwrap();
 
+    let mut tasks = Vec::new();
     for row in db_result {
-      let json_task = json!({
-        "id": row.get::<u32, _>("id"),
-        "name": row.get::<String, _>("name"),
-        "model_name": row.get::<String, _>("model_name"),
-        "model_id": row.get::<u32, _>("model_id"),
-        "type": row.get::<String, _>("type"),
-        "manual_task_owner": row.get::<Option<String>, _>("manual_task_owner"),
-        "status": row.get::<String, _>("status"),
-        "failure_reason": row.get::<Option<String>, _>("failure_reason"),
-        "created_at": row.get::<String, _>("created_at"),
-        "updated_at": row.get::<String, _>("updated_at"),
-      });
-
-      let task = serde_json::from_value::<Task>(json_task);
-      if task.is_err() {
-        tracing::error!("Error while deserializing task: {:?}", task);
-        return Err(DataAccessError::InternalError);
-      }
-      let task = task.unwrap();
-
-      tasks.push(task);
+      tasks.push(get_task_from_row(&row)?);
     }
 
     Ok(tasks)
@@ -160,4 +207,43 @@ impl TaskRepository {
 
     Ok(())
   }
+
+  pub async fn fail_many_tasks_by_ids(
+    &self,
+    ids: Vec<u32>,
+    failure_reason: &str,
+  ) -> Result<(), DataAccessError> {
+    let conn = self.main_sql_db.acquire().await;
+    if conn.is_err() {
+      tracing::error!("Error while getting sql connection: {:?}", conn);
+      return Err(DataAccessError::InternalError);
+    }
+    let mut conn = conn.unwrap();
+
+    let db_result = sqlx::query(
+      format!(
+        r#"
+      UPDATE task
+      SET status = 'Failed', failure_reason = $1, updated_at = strftime('%Y-%m-%dT%H:%M:%S.%fZ', 'now')
+      WHERE id IN ({})
+      "#,
+        ids
+          .iter()
+          .map(|id| id.to_string())
+          .collect::<Vec<String>>()
+          .join(","),
+      )
+      .as_str(),
+    )
+    .bind(failure_reason)
+    .execute(&mut *conn)
+    .await;
+
+    if db_result.is_err() {
+      tracing::error!("Error while failing many tasks: {:?}", db_result);
+      return Err(DataAccessError::InternalError);
+    }
+
+    Ok(())
+  }
 }
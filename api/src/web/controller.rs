@@ -3,9 +3,11 @@ use crate::{
   _utils::{
     database::DBOrderDirection, post_long_title::get_post_long_title, post_url::get_post_url,
   },
+  post::model::{PostModerationStatus, PostTrait},
+  post_stat::{controller::record_one_post_event, model::PostEventKind},
 };
 use axum::{
-  extract::{Path, State},
+  extract::{ConnectInfo, Path, State},
   headers::ContentType,
   response::{Html, IntoResponse},
   Router, TypedHeader,
@@ -13,7 +15,7 @@ use axum::{
 use hyper::StatusCode;
 use serde::Deserialize;
 use sitewriter::{ChangeFreq, UrlEntry, UrlEntryBuilder};
-use std::fs;
+use std::{fs, net::SocketAddr};
 
 #[derive(Deserialize)]
 pub struct EmailQuery {
@@ -81,6 +83,7 @@ pub async fn index(State(app_state): State<AppState>) -> impl IntoResponse {
 }
 
 pub async fn jobs(
+  ConnectInfo(ip): ConnectInfo<SocketAddr>,
   Path(job_slug): Path<String>,
   State(app_state): State<AppState>,
 ) -> impl IntoResponse {
@@ -100,6 +103,11 @@ pub async fn jobs(
   }
   let post = post.unwrap();
 
+  // pending and rejected posts are only shown to their poster and to admins, through the api
+  if post.moderation_status != PostModerationStatus::Approved {
+    return return404(&app_state).into_response();
+  }
+
   let poster = app_state
     .account_repository
     .get_one_account_by_id(post.poster_id)
@@ -109,13 +117,23 @@ pub async fn jobs(
   }
   let poster = poster.unwrap();
 
+  if post.is_live() {
+    record_one_post_event(&app_state, post.id, PostEventKind::View, &ip).await;
+  }
+
   Html(read_html(ReadHtmlParam {
     file_name: format!(
       "{}/index.html",
       app_state.config_service.get_config().html_path
     ),
-    title: get_post_long_title(&post, &poster),
-    description: post.short_description,
+    title: match post.is_archived {
+      true => format!("[Closed] {}", get_post_long_title(&post, &poster)),
+      false => get_post_long_title(&post, &poster),
+    },
+    description: match post.is_archived {
+      true => format!("This position is closed. {}", post.short_description),
+      false => post.short_description,
+    },
     image: format!(
       "https://{}.assets.dzjob.io/assets/apple-touch-startup-image-1136x640.png",
       app_state.config_service.get_config().stage.as_str()